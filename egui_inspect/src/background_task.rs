@@ -1,5 +1,10 @@
-use crate::{utils::type_name_base, EguiInspect, DEFAULT_FRAME_STYLE};
-use egui::ProgressBar;
+use crate::{
+    diff::{diff_columns, diff_group, diff_leaf, DiffKind},
+    utils::{empty_response, type_name_base},
+    validation::ValidationError,
+    EguiInspect, DEFAULT_FRAME_STYLE,
+};
+use egui::{ProgressBar, Response};
 use std::{
     mem,
     sync::{Arc, Mutex},
@@ -69,8 +74,8 @@ impl EguiInspect for SynchedStats {
         });
     }
 
-    fn inspect_mut(&mut self, label: &str, ui: &mut egui::Ui) {
-        self.inspect(label, ui);
+    fn inspect_mut(&mut self, label: &str, ui: &mut egui::Ui) -> Response {
+        ui.scope(|ui| self.inspect(label, ui)).response
     }
 }
 
@@ -106,6 +111,8 @@ enum BackgroundTaskState<T: Task> {
 /// in an egui ui. In the starting state it exposes the initialisation parameters for its
 /// associated task, in the running/ongoing state it shows a progress bar, and in the finished
 /// state it displays the result object and offers to restart.
+///
+/// Begin signals are ignored while the parameters are invalid (see [EguiInspect::is_valid]).
 pub struct BackgroundTask<T: Task, const COMPACT_LABELS: bool = false> {
     state: BackgroundTaskState<T>,
     pub res: Result<T::Return, String>,
@@ -124,14 +131,19 @@ impl<T: Task, const COMPACT_LABELS: bool> Default for BackgroundTask<T, COMPACT_
 macro_rules! base_inspect {
     ($self:ident, $label: ident, $ui: ident, $state_inspect: tt) => {
         if COMPACT_LABELS {
-            $state_inspect($self, "", &format!("{} progress", $label), $ui);
+            let state_response = $state_inspect($self, "", &format!("{} progress", $label), $ui);
             base_inspect!($self, $ui); // res inspect
+            state_response
         } else {
-            DEFAULT_FRAME_STYLE.to_frame().show($ui, |ui| {
-                ui.strong(format!("{} ({})", $label, type_name_base::<T>()));
-                $state_inspect($self, "params", "progress", ui);
-                base_inspect!($self, ui); // res inspect
-            });
+            DEFAULT_FRAME_STYLE
+                .to_frame()
+                .show($ui, |ui| {
+                    ui.strong(format!("{} ({})", $label, type_name_base::<T>()));
+                    let state_response = $state_inspect($self, "params", "progress", ui);
+                    base_inspect!($self, ui); // res inspect
+                    state_response
+                })
+                .inner
         }
     };
     ($self:ident, $ui: ident) => {
@@ -164,26 +176,57 @@ where
         base_inspect!(self, label, ui, state_inspect);
     }
 
-    fn inspect_mut(&mut self, label: &str, ui: &mut egui::Ui) {
-        let state_inspect = |s: &mut Self,
-                             param_label: &str,
-                             prog_label: &str,
-                             ui: &mut egui::Ui| {
-            match &mut s.state {
-                BackgroundTaskState::PendingStart { init_params } => {
-                    init_params.inspect_mut(param_label, ui);
-                    s.poll_ready();
-                }
-                BackgroundTaskState::Restarting => { /* state only briefly used inside poll_ready */
-                }
-                BackgroundTaskState::Ongoing { progress, .. } => {
-                    // progress.0.inspect(&format!("{label} progress"), ui);
-                    progress.0.inspect(prog_label, ui);
-                    s.poll_result();
+    fn inspect_mut(&mut self, label: &str, ui: &mut egui::Ui) -> Response {
+        let state_inspect =
+            |s: &mut Self, param_label: &str, prog_label: &str, ui: &mut egui::Ui| {
+                match &mut s.state {
+                    BackgroundTaskState::PendingStart { init_params } => {
+                        let response = init_params.inspect_mut(param_label, ui);
+                        s.poll_ready();
+                        response
+                    }
+                    BackgroundTaskState::Restarting => {
+                        /* state only briefly used inside poll_ready */
+                        empty_response(ui)
+                    }
+                    BackgroundTaskState::Ongoing { progress, .. } => {
+                        // progress.0.inspect(&format!("{label} progress"), ui);
+                        progress.0.inspect(prog_label, ui);
+                        s.poll_result();
+                        empty_response(ui)
+                    }
                 }
+            };
+        base_inspect!(self, label, ui, state_inspect)
+    }
+
+    fn is_inline(&self) -> bool {
+        false
+    }
+
+    fn validation_errors(&self) -> Vec<ValidationError> {
+        match &self.state {
+            BackgroundTaskState::PendingStart { init_params } => init_params.validation_errors(),
+            _ => vec![],
+        }
+    }
+
+    /// Compares the results of the two tasks
+    fn inspect_diff(&self, other: &Self, label: &str, ui: &mut egui::Ui) -> bool {
+        diff_group(label, ui, |ui| match (&self.res, &other.res) {
+            (Ok(l), Ok(r)) => l.inspect_diff(r, "previous result", ui),
+            (Err(l), Err(r)) => diff_leaf(l, r, "previous error", ui),
+            _ => {
+                let (left, right) = (self, other);
+                diff_columns(
+                    ui,
+                    DiffKind::Changed,
+                    |ui| base_inspect!(left, ui),
+                    |ui| base_inspect!(right, ui),
+                );
+                true
             }
-        };
-        base_inspect!(self, label, ui, state_inspect);
+        })
     }
 }
 
@@ -202,7 +245,9 @@ where
     }
     fn poll_ready(&mut self) {
         let ssopts = match &self.state {
-            BackgroundTaskState::PendingStart { init_params } => init_params.begin_signal(),
+            BackgroundTaskState::PendingStart { init_params } if init_params.is_valid() => {
+                init_params.begin_signal()
+            }
             _ => None,
         };
         if let Some(ssopts) = ssopts {
@@ -1,8 +1,23 @@
 use std::cell::RefCell;
-use std::collections::{BTreeMap, HashMap};
+use std::collections::{BTreeMap, BTreeSet, HashMap, VecDeque};
 use std::rc::Rc;
 use std::sync::{Arc, Mutex};
 
+use egui::{CollapsingHeader, CollapsingResponse, Response};
+
+use crate::code_editor;
+use crate::diff::{diff_group, diff_leaf, diff_one_sided};
+use crate::filter::{contains_match, force_open, highlighted_label, label_matches, show_field};
+use crate::help::{attach_doc, field_label, show_doc, take_doc};
+use crate::markdown;
+use crate::options::{NumberBound, NumberFormat};
+use crate::pattern;
+use crate::utils::{collapsing_response, empty_response};
+use crate::validation::{prefix_errors, show_validation_error, ValidationError};
+use crate::vec_editor::{sequence_inspect, sequence_inspect_mut};
+use crate::widget_state::with_state;
+use crate::InspectOptions;
+
 macro_rules! impl_inspect_num {
     ($($t:ty),+) => {
         $(
@@ -10,18 +25,32 @@ macro_rules! impl_inspect_num {
                 fn inspect(&self, label: &str, ui: &mut egui::Ui) {
                     ui.horizontal(|ui| {
                         if !label.is_empty() {
-                            ui.label(label.to_owned() + ":");
+                            field_label(ui, label, ":");
                         }
                         ui.label(self.to_string());
                     });
                 }
-                fn inspect_mut(&mut self, label: &str, ui: &mut egui::Ui) {
+                fn inspect_mut(&mut self, label: &str, ui: &mut egui::Ui) -> Response {
+                    self.inspect_mut_with(label, ui, &InspectOptions::default())
+                }
+
+                fn inspect_mut_with(
+                    &mut self,
+                    label: &str,
+                    ui: &mut egui::Ui,
+                    options: &InspectOptions,
+                ) -> Response {
                     ui.horizontal(|ui| {
                         if !label.is_empty() {
-                            ui.label(label.to_owned() + ":");
+                            field_label(ui, label, ":");
                         }
-                        ui.add(egui::DragValue::new(self).max_decimals(10));
-                    });
+                        number_inspect_mut(self, ui, options)
+                    })
+                    .inner
+                }
+
+                fn inspect_diff(&self, other: &Self, label: &str, ui: &mut egui::Ui) -> bool {
+                    diff_leaf(self, other, label, ui)
                 }
             }
         )*
@@ -30,171 +59,577 @@ macro_rules! impl_inspect_num {
 
 impl_inspect_num!(f32, f64, i8, u8, i16, u16, i32, u32, i64, u64, isize, usize);
 
+/// Conversion of the [NumberBound]s of the options to a number type, saturating at its limits
+trait FromBound {
+    fn from_bound(bound: NumberBound) -> Self;
+}
+
+macro_rules! impl_from_bound {
+    ($($t:ty),+) => {
+        $(
+            impl FromBound for $t {
+                fn from_bound(bound: NumberBound) -> Self {
+                    match bound {
+                        // NOTE: integers are compared as i128, so bounds are kept exactly
+                        NumberBound::Int(i) if <$t as egui::emath::Numeric>::INTEGRAL => {
+                            i.clamp(<$t>::MIN as i128, <$t>::MAX as i128) as $t
+                        }
+                        NumberBound::Int(i) => i as $t,
+                        NumberBound::Float(f) => f as $t,
+                    }
+                }
+            }
+        )*
+    }
+}
+
+impl_from_bound!(f32, f64, i8, u8, i16, u16, i32, u32, i64, u64, isize, usize);
+
+/// Slider or drag value of a number, as configured by the options
+fn number_inspect_mut<T: egui::emath::Numeric + FromBound>(
+    value: &mut T,
+    ui: &mut egui::Ui,
+    options: &InspectOptions,
+) -> Response {
+    let min = options.min.map(T::from_bound);
+    let max = options.max.map(T::from_bound);
+    let integral = T::INTEGRAL;
+
+    // NOTE: both widgets take the same configuration, through differently typed builders
+    macro_rules! configure {
+        ($widget:expr) => {{
+            let mut widget = $widget;
+            if let Some(prefix) = &options.prefix {
+                widget = widget.prefix(prefix);
+            }
+            if let Some(suffix) = &options.suffix {
+                widget = widget.suffix(suffix);
+            }
+            if let Some(decimals) = options.decimals {
+                widget = widget.fixed_decimals(decimals);
+            }
+            match options.format {
+                NumberFormat::Hex if integral => widget = widget.hexadecimal(1, false, true),
+                NumberFormat::Binary if integral => widget = widget.binary(1, false),
+                format => {
+                    if let Some((formatter, parser)) = format.text_conversions(options.decimals) {
+                        widget = widget.custom_formatter(formatter).custom_parser(parser);
+                    }
+                }
+            }
+            widget
+        }};
+    }
+
+    let mut response = if options.slider || options.log_slider {
+        let range = min.unwrap_or(T::from_f64(0.0))..=max.unwrap_or(T::from_f64(100.0));
+        let mut slider = egui::Slider::new(value, range)
+            .logarithmic(options.log_slider)
+            .clamping(options.clamping);
+        if let Some(step) = options.step {
+            slider = slider.step_by(step);
+        }
+        if let Some(speed) = options.speed {
+            slider = slider.drag_value_speed(speed);
+        }
+        ui.add(configure!(slider))
+    } else {
+        let mut drag_value = egui::DragValue::new(value).max_decimals(10);
+        let bounded = min.is_some() || max.is_some();
+        if bounded && options.clamping != egui::SliderClamping::Never {
+            drag_value = drag_value
+                .range(min.unwrap_or(T::MIN)..=max.unwrap_or(T::MAX))
+                .clamp_existing_to_range(options.clamping == egui::SliderClamping::Always);
+        }
+        // NOTE: a step with no explicit speed moves by about one step per point dragged
+        if let Some(speed) = options.speed.or(options.step) {
+            drag_value = drag_value.speed(speed);
+        }
+        ui.add(configure!(drag_value))
+    };
+
+    let mut exact = *value;
+    if let Some(step) = options
+        .step
+        .filter(|step| *step > 0.0 && response.changed())
+    {
+        let origin = min.map_or(0.0, T::to_f64);
+        exact = T::from_f64(origin + ((exact.to_f64() - origin) / step).round() * step);
+    }
+    // NOTE: egui compares bounds as f64, so integers are clamped again, exactly
+    let clamp = match options.clamping {
+        egui::SliderClamping::Always => true,
+        egui::SliderClamping::Edits => response.changed(),
+        egui::SliderClamping::Never => false,
+    };
+    if clamp {
+        if let Some(min) = min.filter(|min| exact < *min) {
+            exact = min;
+        }
+        if let Some(max) = max.filter(|max| exact > *max) {
+            exact = max;
+        }
+    }
+    if exact != *value {
+        *value = exact;
+        response.mark_changed();
+    }
+    response
+}
+
 impl crate::EguiInspect for &'static str {
     fn inspect(&self, label: &str, ui: &mut egui::Ui) {
-        ui.horizontal(|ui| {
-            if !label.is_empty() {
-                ui.label(label.to_owned() + ":");
-            }
-            ui.label(self.to_string());
-        });
+        self.inspect_with(label, ui, &InspectOptions::default());
+    }
+
+    fn inspect_with(&self, label: &str, ui: &mut egui::Ui, options: &InspectOptions) {
+        str_inspect_with(self, label, ui, options);
     }
-    fn inspect_mut(&mut self, label: &str, ui: &mut egui::Ui) {
+    fn inspect_mut(&mut self, label: &str, ui: &mut egui::Ui) -> Response {
         ui.horizontal(|ui| {
             if !label.is_empty() {
-                ui.label(label.to_owned() + ":");
+                field_label(ui, label, ":");
             }
             ui.colored_label(egui::Color32::from_rgb(255, 0, 0), self.to_string())
-                .on_hover_text("inspect_mut is not implemented for &'static str");
-        });
+                .on_hover_text("inspect_mut is not implemented for &'static str")
+        })
+        .inner
+    }
+
+    fn inspect_diff(&self, other: &Self, label: &str, ui: &mut egui::Ui) -> bool {
+        diff_leaf(self, other, label, ui)
     }
 }
 
 impl crate::EguiInspect for String {
     fn inspect(&self, label: &str, ui: &mut egui::Ui) {
-        ui.horizontal(|ui| {
-            if !label.is_empty() {
-                ui.label(label.to_owned() + ":");
-            }
-            ui.label(self);
-        });
+        self.inspect_with(label, ui, &InspectOptions::default());
+    }
+
+    fn inspect_with(&self, label: &str, ui: &mut egui::Ui, options: &InspectOptions) {
+        str_inspect_with(self, label, ui, options);
+    }
+    fn inspect_mut(&mut self, label: &str, ui: &mut egui::Ui) -> Response {
+        str_inspect_mut_singleline(self, label, ui)
     }
-    fn inspect_mut(&mut self, label: &str, ui: &mut egui::Ui) {
-        str_inspect_mut_singleline(self, label, ui);
+
+    fn inspect_mut_with(
+        &mut self,
+        label: &str,
+        ui: &mut egui::Ui,
+        options: &InspectOptions,
+    ) -> Response {
+        str_inspect_mut_with(self, label, ui, options)
+    }
+
+    fn inspect_diff(&self, other: &Self, label: &str, ui: &mut egui::Ui) -> bool {
+        diff_leaf(self, other, label, ui)
     }
 }
 
-pub fn str_inspect_mut_multiline(s: &mut String, label: &str, ui: &mut egui::Ui) {
+pub fn str_inspect_mut_multiline(s: &mut String, label: &str, ui: &mut egui::Ui) -> Response {
     ui.horizontal(|ui| {
         if !label.is_empty() {
-            ui.label(label.to_owned() + ":");
+            field_label(ui, label, ":");
         }
-        ui.text_edit_multiline(s);
-    });
+        ui.text_edit_multiline(s)
+    })
+    .inner
+}
+
+pub fn str_inspect_mut_singleline(s: &mut String, label: &str, ui: &mut egui::Ui) -> Response {
+    ui.horizontal(|ui| {
+        if !label.is_empty() {
+            field_label(ui, label, ":");
+        }
+        ui.text_edit_singleline(s)
+    })
+    .inner
+}
+
+/// Text edit configured by the string options (`multiline`, `password`, `hint`, `max_len`,
+/// `charset` and `regex`). Edits are filtered down to the allowed characters (and to a single
+/// line, unless `multiline`), while a mismatch of the pattern is only flagged. With `code`, the
+/// string is edited in a [code_editor] instead, and with `markdown` next to its rendering.
+pub fn str_inspect_mut_with(
+    s: &mut String,
+    label: &str,
+    ui: &mut egui::Ui,
+    options: &InspectOptions,
+) -> Response {
+    if let Some(language) = &options.code {
+        return code_editor::code_edit(s, label, ui, language);
+    }
+    if options.markdown {
+        return markdown::markdown_edit(s, label, ui);
+    }
+    ui.horizontal(|ui| {
+        if !label.is_empty() {
+            field_label(ui, label, ":");
+        }
+        let mut edit = match options.multiline {
+            true => egui::TextEdit::multiline(s),
+            false => egui::TextEdit::singleline(s),
+        }
+        .password(options.password);
+        if let Some(hint) = &options.hint {
+            edit = edit.hint_text(hint.as_str());
+        }
+        // NOTE: egui would cut pasted text before dropping the disallowed characters
+        if let Some(max_len) = options.max_len.filter(|_| options.charset.is_none()) {
+            edit = edit.char_limit(max_len);
+        }
+        let response = ui.add(edit);
+
+        if response.changed() {
+            let allowed = |c: &char| {
+                options.charset.is_none_or(|charset| charset.allows(*c))
+                    && (options.multiline || !matches!(c, '\n' | '\r'))
+            };
+            let max_len = options.max_len.unwrap_or(usize::MAX);
+            *s = s.chars().filter(allowed).take(max_len).collect();
+        }
+        if let Some(regex) = &options.regex {
+            let mismatch = match pattern::cached(ui, regex) {
+                Ok(pattern) => (!pattern.is_match(s)).then(|| format!("does not match {regex}")),
+                Err(e) => Some(format!("invalid regex {regex}: {e}")),
+            };
+            if let Some(message) = mismatch {
+                show_validation_error(ui, response.rect, &message);
+            }
+        }
+        response
+    })
+    .inner
+}
+
+/// Read-only view of a string as configured by the string options: in a [code_editor] view with
+/// `code`, rendered with `markdown`, masked with `password`, else as a label
+pub fn str_inspect_with(s: &str, label: &str, ui: &mut egui::Ui, options: &InspectOptions) {
+    if let Some(language) = &options.code {
+        code_editor::code_view(s, label, ui, language);
+    } else if options.markdown {
+        markdown::markdown_view(s, label, ui);
+    } else if options.password {
+        password_inspect(s, label, ui);
+    } else {
+        ui.horizontal(|ui| {
+            if !label.is_empty() {
+                field_label(ui, label, ":");
+            }
+            ui.label(s);
+        });
+    }
 }
 
-pub fn str_inspect_mut_singleline(s: &mut String, label: &str, ui: &mut egui::Ui) {
+/// Read-only view of a `password` string, masked
+pub fn password_inspect(s: &str, label: &str, ui: &mut egui::Ui) {
     ui.horizontal(|ui| {
         if !label.is_empty() {
-            ui.label(label.to_owned() + ":");
+            field_label(ui, label, ":");
         }
-        ui.text_edit_singleline(s);
+        ui.label("•".repeat(s.chars().count()));
     });
 }
 
 impl crate::EguiInspect for bool {
     fn inspect(&self, label: &str, ui: &mut egui::Ui) {
-        ui.add_enabled(false, egui::Checkbox::new(&mut self.clone(), label));
+        let text = highlighted_label(ui, label, "");
+        let response = ui.add_enabled(false, egui::Checkbox::new(&mut self.clone(), text));
+        attach_doc(ui, response);
     }
-    fn inspect_mut(&mut self, label: &str, ui: &mut egui::Ui) {
-        ui.checkbox(self, label);
+    fn inspect_mut(&mut self, label: &str, ui: &mut egui::Ui) -> Response {
+        let text = highlighted_label(ui, label, "");
+        let response = ui.checkbox(self, text);
+        attach_doc(ui, response)
     }
+
+    fn inspect_diff(&self, other: &Self, label: &str, ui: &mut egui::Ui) -> bool {
+        diff_leaf(self, other, label, ui)
+    }
+}
+
+/// Collapsing header of a container field, highlighted by the active filter (see
+/// [crate::filter]), showing the field's doc on hover (see [crate::help])
+pub(crate) fn show_collapsing<R>(
+    ui: &mut egui::Ui,
+    label: &str,
+    suffix: &str,
+    open: Option<bool>,
+    body: impl FnOnce(&mut egui::Ui) -> R,
+) -> CollapsingResponse<R> {
+    let doc = take_doc(ui);
+    let mut collapsing = CollapsingHeader::new(highlighted_label(ui, label, suffix))
+        .id_salt(label)
+        .open(open)
+        .show(ui, body);
+    collapsing.header_response = show_doc(collapsing.header_response, doc);
+    collapsing
 }
 
 impl<T: crate::EguiInspect, const N: usize> crate::EguiInspect for [T; N] {
     fn inspect(&self, label: &str, ui: &mut egui::Ui) {
-        let n = self.len();
-        ui.collapsing(format!("{label} (len {n})"), |ui| {
-            for (i, item) in self.iter().enumerate() {
-                item.inspect(format!("{label}[{i}]").as_str(), ui);
-            }
-        });
+        self.inspect_with(label, ui, &InspectOptions::default());
     }
 
-    fn inspect_mut(&mut self, label: &str, ui: &mut egui::Ui) {
+    fn inspect_with(&self, label: &str, ui: &mut egui::Ui, options: &InspectOptions) {
         let n = self.len();
-        ui.collapsing(format!("{label} (len {n})"), |ui| {
-            for (i, item) in self.iter_mut().enumerate() {
-                item.inspect_mut(format!("{label}[{i}]").as_str(), ui);
+        let open = force_open(ui, self);
+        show_collapsing(ui, label, &format!(" (len {n})"), open, |ui| {
+            for (i, item) in self.iter().enumerate() {
+                let item_label = format!("{label}[{i}]");
+                let contains_match = contains_match(ui, item);
+                show_field(ui, &item_label, contains_match, |ui| {
+                    ui.push_id(i, |ui| item.inspect_with(&item_label, ui, options));
+                });
             }
         });
     }
-}
 
-impl<T: crate::EguiInspect + Default> crate::EguiInspect for Vec<T> {
-    fn inspect(&self, label: &str, ui: &mut egui::Ui) {
-        ui.collapsing(label, |ui| {
-            for (i, item) in self.iter().enumerate() {
-                item.inspect(format!("{label}[{i}]").as_str(), ui);
-            }
-        });
+    fn inspect_mut(&mut self, label: &str, ui: &mut egui::Ui) -> Response {
+        self.inspect_mut_with(label, ui, &InspectOptions::default())
     }
 
-    fn inspect_mut(&mut self, label: &str, ui: &mut egui::Ui) {
+    fn inspect_mut_with(
+        &mut self,
+        label: &str,
+        ui: &mut egui::Ui,
+        options: &InspectOptions,
+    ) -> Response {
         let n = self.len();
-        ui.collapsing(label, |ui| {
-            let mut to_remove = None;
-            let mut to_swap = None;
-            for (i, item) in self.iter_mut().enumerate() {
-                item.inspect_mut(format!("{label}[{i}]").as_str(), ui);
-
-                ui.horizontal_top(|ui| {
-                    if ui.button("Remove").clicked() {
-                        to_remove = Some(i);
+        let open = force_open(ui, self);
+        collapsing_response(show_collapsing(
+            ui,
+            label,
+            &format!(" (len {n})"),
+            open,
+            |ui| {
+                let mut response = empty_response(ui);
+                for (i, item) in self.iter_mut().enumerate() {
+                    let item_label = format!("{label}[{i}]");
+                    let contains_match = contains_match(ui, item);
+                    if let Some(item_response) = show_field(ui, &item_label, contains_match, |ui| {
+                        ui.push_id(i, |ui| item.inspect_mut_with(&item_label, ui, options))
+                            .inner
+                    }) {
+                        response |= item_response;
                     }
+                }
+                response
+            },
+        ))
+    }
 
-                    if i < n - 1 && ui.button("Swap with next").clicked() {
-                        to_swap = Some(i);
-                    }
-                });
-            }
+    fn is_inline(&self) -> bool {
+        false
+    }
 
-            if let Some(i) = to_remove {
-                self.remove(i);
-            }
-            if let Some(i) = to_swap {
-                let e = self.remove(i);
-                self.insert(i + 1, e);
-            }
+    fn matches_filter(&self, query: &str) -> bool {
+        self.iter().any(|item| item.matches_filter(query))
+    }
 
-            if ui.button("Push default").clicked() {
-                self.push(T::default());
-            }
-        });
+    fn validation_errors(&self) -> Vec<ValidationError> {
+        self.iter()
+            .enumerate()
+            .flat_map(|(i, item)| prefix_errors(item.validation_errors(), &format!("[{i}]")))
+            .collect()
+    }
+
+    fn inspect_diff(&self, other: &Self, label: &str, ui: &mut egui::Ui) -> bool {
+        diff_sequences(self, other, label, ui)
     }
 }
 
-thread_local! {
-    pub static NEW_KEY: RefCell<String> = Default::default();
+/// Diff of the entries under the union of both keys, in order
+pub(crate) fn diff_entries<'a, T: crate::EguiInspect + 'a>(
+    keys: BTreeSet<&String>,
+    left: impl Fn(&String) -> Option<&'a T>,
+    right: impl Fn(&String) -> Option<&'a T>,
+    label: &str,
+    ui: &mut egui::Ui,
+) -> bool {
+    diff_group(label, ui, |ui| {
+        let mut differs = false;
+        for key in keys {
+            ui.push_id(key, |ui| match (left(key), right(key)) {
+                (Some(l), Some(r)) => differs |= l.inspect_diff(r, key, ui),
+                (Some(l), None) => {
+                    diff_one_sided(l, false, key, ui);
+                    differs = true;
+                }
+                (None, Some(r)) => {
+                    diff_one_sided(r, true, key, ui);
+                    differs = true;
+                }
+                (None, None) => {}
+            });
+        }
+        differs
+    })
+}
+
+/// Element-wise diff, with the trailing elements of the longer sequence shown as added/removed
+pub(crate) fn diff_sequences<'a, T: crate::EguiInspect + 'a>(
+    left: impl IntoIterator<Item = &'a T>,
+    right: impl IntoIterator<Item = &'a T>,
+    label: &str,
+    ui: &mut egui::Ui,
+) -> bool {
+    let (left, right): (Vec<&T>, Vec<&T>) =
+        (left.into_iter().collect(), right.into_iter().collect());
+    diff_group(label, ui, |ui| {
+        let mut differs = left.len() != right.len();
+        for i in 0..left.len().max(right.len()) {
+            let item_label = format!("{label}[{i}]");
+            ui.push_id(i, |ui| match (left.get(i), right.get(i)) {
+                (Some(l), Some(r)) => differs |= l.inspect_diff(r, &item_label, ui),
+                (Some(l), None) => diff_one_sided(*l, false, &item_label, ui),
+                (None, Some(r)) => diff_one_sided(*r, true, &item_label, ui),
+                (None, None) => {}
+            });
+        }
+        differs
+    })
+}
+
+macro_rules! impl_inspect_seq {
+    ($($t:ident),+) => {
+        $(
+            impl<T: crate::EguiInspect + Default> crate::EguiInspect for $t<T> {
+                fn inspect(&self, label: &str, ui: &mut egui::Ui) {
+                    self.inspect_with(label, ui, &InspectOptions::default());
+                }
+
+                fn inspect_with(&self, label: &str, ui: &mut egui::Ui, options: &InspectOptions) {
+                    sequence_inspect(self, label, ui, options);
+                }
+
+                fn inspect_mut(&mut self, label: &str, ui: &mut egui::Ui) -> Response {
+                    self.inspect_mut_with(label, ui, &InspectOptions::default())
+                }
+
+                fn inspect_mut_with(
+                    &mut self,
+                    label: &str,
+                    ui: &mut egui::Ui,
+                    options: &InspectOptions,
+                ) -> Response {
+                    sequence_inspect_mut(self, label, ui, options, None)
+                }
+
+                fn is_inline(&self) -> bool {
+                    false
+                }
+
+                fn matches_filter(&self, query: &str) -> bool {
+                    self.iter().any(|item| item.matches_filter(query))
+                }
+
+                fn validation_errors(&self) -> Vec<ValidationError> {
+                    self.iter()
+                        .enumerate()
+                        .flat_map(|(i, item)| {
+                            prefix_errors(item.validation_errors(), &format!("[{i}]"))
+                        })
+                        .collect()
+                }
+
+                fn inspect_diff(&self, other: &Self, label: &str, ui: &mut egui::Ui) -> bool {
+                    diff_sequences(self, other, label, ui)
+                }
+            }
+        )*
+    };
 }
 
+impl_inspect_seq!(Vec, VecDeque);
+
 macro_rules! impl_inspect_map {
     ($($t:ident),+) => {
         $(
         impl<T: crate::EguiInspect + Default> crate::EguiInspect for $t<String, T> {
             fn inspect(&self, label: &str, ui: &mut egui::Ui) {
-                ui.collapsing(format!("{label}"), |ui| {
-                    for (key, item) in self.iter() {
-                        item.inspect(key.as_str(), ui);
-                    }
-                });
+                self.inspect_with(label, ui, &InspectOptions::default());
+            }
+
+            fn inspect_with(&self, label: &str, ui: &mut egui::Ui, options: &InspectOptions) {
+                let open = force_open(ui, self);
+                show_collapsing(ui, label, "", open, |ui| {
+                        for (key, item) in self.iter() {
+                            let contains_match = contains_match(ui, item);
+                            show_field(ui, key, contains_match, |ui| {
+                                ui.push_id(key, |ui| item.inspect_with(key.as_str(), ui, options));
+                            });
+                        }
+                    });
+            }
+
+            fn inspect_mut(&mut self, label: &str, ui: &mut egui::Ui) -> Response {
+                self.inspect_mut_with(label, ui, &InspectOptions::default())
             }
 
-            fn inspect_mut(&mut self, label: &str, ui: &mut egui::Ui) {
-                ui.collapsing(format!("{label}"), |ui| {
+            fn inspect_mut_with(
+                &mut self,
+                label: &str,
+                ui: &mut egui::Ui,
+                options: &InspectOptions,
+            ) -> Response {
+                let open = force_open(ui, self);
+                collapsing_response(show_collapsing(ui, label, "", open, |ui| {
+                    let mut response = empty_response(ui);
                     let mut to_remove = None;
                     for (key, item) in self.iter_mut() {
-                        item.inspect_mut(key.as_str(), ui);
+                        let contains_match = contains_match(ui, item);
+                        show_field(ui, key, contains_match, |ui| {
+                            response |= ui
+                                .push_id(key, |ui| item.inspect_mut_with(key.as_str(), ui, options))
+                                .inner;
 
-                        if ui.button("Remove").clicked() {
-                            to_remove = Some(key.clone());
-                        }
+                            if ui.button("Remove").clicked() {
+                                to_remove = Some(key.clone());
+                            }
+                        });
                     }
 
                     if let Some(key) = to_remove {
                         self.remove(&key);
+                        response.mark_changed();
                     }
 
+                    // NOTE: the menu lives on another layer, so its responses are not merged in
+                    let new_key_id = ui.id().with("new_key");
                     ui.menu_button("Insert default", |ui| {
-                        NEW_KEY.with_borrow_mut(|s| {
+                        with_state(ui, new_key_id, |s: &mut String, ui| {
                             s.inspect_mut("new key", ui);
                             if ui.button("Insert").clicked() {
-                                self.insert(s.clone(), T::default());
+                                self.insert(std::mem::take(s), T::default());
+                                response.mark_changed();
                                 ui.close();
                             }
                         });
                     });
-                });
+                    response
+                }))
+            }
+
+            fn is_inline(&self) -> bool {
+                false
+            }
+
+            fn matches_filter(&self, query: &str) -> bool {
+                self.iter()
+                    .any(|(key, item)| label_matches(key, query) || item.matches_filter(query))
+            }
+
+            fn validation_errors(&self) -> Vec<ValidationError> {
+                self.iter()
+                    .flat_map(|(key, item)| prefix_errors(item.validation_errors(), &format!("[{key:?}]")))
+                    .collect()
+            }
+
+            fn inspect_diff(&self, other: &Self, label: &str, ui: &mut egui::Ui) -> bool {
+                let keys = self.keys().chain(other.keys()).collect();
+                diff_entries(keys, |k| self.get(k), |k| other.get(k), label, ui)
             }
         }
         )*
@@ -205,9 +640,13 @@ impl_inspect_map!(HashMap, BTreeMap);
 
 impl<T: crate::EguiInspect + Default> crate::EguiInspect for Option<T> {
     fn inspect(&self, label: &str, ui: &mut egui::Ui) {
+        self.inspect_with(label, ui, &InspectOptions::default());
+    }
+
+    fn inspect_with(&self, label: &str, ui: &mut egui::Ui, options: &InspectOptions) {
         match self {
             Some(v) => {
-                v.inspect(label, ui);
+                v.inspect_with(label, ui, options);
             }
             None => {
                 ui.label(format!("{label} is None").as_str());
@@ -215,52 +654,278 @@ impl<T: crate::EguiInspect + Default> crate::EguiInspect for Option<T> {
         }
     }
 
-    fn inspect_mut(&mut self, label: &str, ui: &mut egui::Ui) {
+    fn inspect_mut(&mut self, label: &str, ui: &mut egui::Ui) -> Response {
+        self.inspect_mut_with(label, ui, &InspectOptions::default())
+    }
+
+    fn inspect_mut_with(
+        &mut self,
+        label: &str,
+        ui: &mut egui::Ui,
+        options: &InspectOptions,
+    ) -> Response {
         ui.horizontal_top(|ui| match self {
             Some(v) => {
-                ui.vertical(|ui| {
-                    v.inspect_mut(label, ui);
-                });
+                let mut response = ui
+                    .vertical(|ui| v.inspect_mut_with(label, ui, options))
+                    .inner;
                 if ui.button("Set to None").clicked() {
                     *self = None;
+                    response.mark_changed();
                 }
+                response
             }
             None => {
-                ui.label(format!("\"{label}\" is None").as_str());
+                let mut response = ui.label(format!("\"{label}\" is None").as_str());
                 if ui.button("Set to default").clicked() {
                     *self = Some(T::default());
+                    response.mark_changed();
                 }
+                response
             }
-        });
+        })
+        .inner
+    }
+
+    fn is_inline(&self) -> bool {
+        self.as_ref().is_none_or(|v| v.is_inline())
+    }
+
+    fn matches_filter(&self, query: &str) -> bool {
+        self.as_ref().is_some_and(|v| v.matches_filter(query))
+    }
+
+    fn validation_errors(&self) -> Vec<ValidationError> {
+        self.as_ref()
+            .map(|v| v.validation_errors())
+            .unwrap_or_default()
+    }
+
+    fn inspect_diff(&self, other: &Self, label: &str, ui: &mut egui::Ui) -> bool {
+        match (self, other) {
+            (Some(l), Some(r)) => l.inspect_diff(r, label, ui),
+            (Some(l), None) => {
+                diff_one_sided(l, false, label, ui);
+                true
+            }
+            (None, Some(r)) => {
+                diff_one_sided(r, true, label, ui);
+                true
+            }
+            (None, None) => {
+                self.inspect(label, ui);
+                false
+            }
+        }
     }
 }
 
 impl<T: crate::EguiInspect> crate::EguiInspect for Arc<Mutex<T>> {
     fn inspect(&self, label: &str, ui: &mut egui::Ui) {
+        self.inspect_with(label, ui, &InspectOptions::default());
+    }
+
+    fn inspect_with(&self, label: &str, ui: &mut egui::Ui, options: &InspectOptions) {
         if let Ok(guard) = self.try_lock() {
-            guard.inspect(label, ui);
+            guard.inspect_with(label, ui, options);
         }
     }
 
-    fn inspect_mut(&mut self, label: &str, ui: &mut egui::Ui) {
-        if let Ok(mut guard) = self.try_lock() {
-            guard.inspect_mut(label, ui);
+    fn inspect_mut(&mut self, label: &str, ui: &mut egui::Ui) -> Response {
+        self.inspect_mut_with(label, ui, &InspectOptions::default())
+    }
+
+    fn inspect_mut_with(
+        &mut self,
+        label: &str,
+        ui: &mut egui::Ui,
+        options: &InspectOptions,
+    ) -> Response {
+        match self.try_lock() {
+            Ok(mut guard) => guard.inspect_mut_with(label, ui, options),
+            Err(_) => empty_response(ui),
+        }
+    }
+
+    fn is_inline(&self) -> bool {
+        self.try_lock().is_ok_and(|guard| guard.is_inline())
+    }
+
+    fn matches_filter(&self, query: &str) -> bool {
+        self.try_lock()
+            .is_ok_and(|guard| guard.matches_filter(query))
+    }
+
+    fn validation_errors(&self) -> Vec<ValidationError> {
+        self.try_lock()
+            .map(|guard| guard.validation_errors())
+            .unwrap_or_default()
+    }
+
+    fn inspect_diff(&self, other: &Self, label: &str, ui: &mut egui::Ui) -> bool {
+        if Arc::ptr_eq(self, other) {
+            self.inspect(label, ui);
+            return false;
+        }
+        match (self.try_lock(), other.try_lock()) {
+            (Ok(l), Ok(r)) => l.inspect_diff(&r, label, ui),
+            _ => false,
         }
     }
 }
 
 impl<T: crate::EguiInspect> crate::EguiInspect for Rc<RefCell<T>> {
     fn inspect(&self, label: &str, ui: &mut egui::Ui) {
+        self.inspect_with(label, ui, &InspectOptions::default());
+    }
+
+    fn inspect_with(&self, label: &str, ui: &mut egui::Ui, options: &InspectOptions) {
         if let Ok(guard) = self.try_borrow() {
-            guard.inspect(label, ui);
+            guard.inspect_with(label, ui, options);
+        }
+    }
+
+    fn inspect_mut(&mut self, label: &str, ui: &mut egui::Ui) -> Response {
+        self.inspect_mut_with(label, ui, &InspectOptions::default())
+    }
+
+    fn inspect_mut_with(
+        &mut self,
+        label: &str,
+        ui: &mut egui::Ui,
+        options: &InspectOptions,
+    ) -> Response {
+        match self.try_borrow_mut() {
+            Ok(mut guard) => guard.inspect_mut_with(label, ui, options),
+            Err(_) => empty_response(ui),
         }
     }
 
-    fn inspect_mut(&mut self, label: &str, ui: &mut egui::Ui) {
-        if let Ok(mut guard) = self.try_borrow_mut() {
-            guard.inspect_mut(label, ui);
+    fn is_inline(&self) -> bool {
+        self.try_borrow().is_ok_and(|guard| guard.is_inline())
+    }
+
+    fn matches_filter(&self, query: &str) -> bool {
+        self.try_borrow()
+            .is_ok_and(|guard| guard.matches_filter(query))
+    }
+
+    fn validation_errors(&self) -> Vec<ValidationError> {
+        self.try_borrow()
+            .map(|guard| guard.validation_errors())
+            .unwrap_or_default()
+    }
+
+    fn inspect_diff(&self, other: &Self, label: &str, ui: &mut egui::Ui) -> bool {
+        match (self.try_borrow(), other.try_borrow()) {
+            (Ok(l), Ok(r)) => l.inspect_diff(&r, label, ui),
+            _ => false,
         }
     }
 }
 
-impl crate::EguiInspect for () {}
+impl crate::EguiInspect for () {
+    fn inspect_mut(&mut self, _label: &str, ui: &mut egui::Ui) -> Response {
+        empty_response(ui)
+    }
+
+    fn inspect_diff(&self, _other: &Self, _label: &str, _ui: &mut egui::Ui) -> bool {
+        false
+    }
+}
+
+#[test]
+fn slider_is_linear() {
+    let options = InspectOptions {
+        slider: true,
+        max: Some(100.0.into()),
+        ..Default::default()
+    };
+    let mut harness = crate::testing::Harness::new(0.0f32)
+        .with_label("x")
+        .with_options(options);
+    // clicking the middle of a linear slider sets half its range
+    assert!(harness.click("x"));
+    assert!((harness.value - 50.0).abs() < 2.0, "{}", harness.value);
+}
+
+#[test]
+fn integer_bounds_are_exact_and_steps_snap() {
+    let options = InspectOptions {
+        max: Some(NumberBound::Int(u64::MAX as i128 - 1)),
+        ..Default::default()
+    };
+    let harness = crate::testing::Harness::new(u64::MAX).with_options(options);
+    assert_eq!(harness.value, u64::MAX - 1);
+
+    let options = InspectOptions {
+        min: Some(NumberBound::Int(0)),
+        max: Some(NumberBound::Int(100)),
+        step: Some(5.0),
+        clamping: egui::SliderClamping::Edits,
+        format: NumberFormat::Percent,
+        ..Default::default()
+    };
+    let mut harness = crate::testing::Harness::new(200i32)
+        .with_label("n")
+        .with_options(options);
+    assert!(!harness.run(), "existing values are kept");
+    assert!(harness.type_text("n", "4300%"));
+    assert_eq!(harness.value, 45);
+    assert!(harness.type_text("n", "43000"));
+    assert_eq!(harness.value, 100);
+}
+
+#[test]
+fn drag_value_is_dragged_and_typed_into() {
+    let mut harness = crate::testing::Harness::new(10i32).with_label("n");
+    assert!(harness.drag("n", egui::Vec2::new(20.0, 0.0)));
+    assert!(harness.value > 10);
+    assert!(harness.type_text("n", "-3"));
+    assert_eq!(harness.value, -3);
+    assert!(!harness.run());
+}
+
+#[test]
+fn checkbox_and_text_edits() {
+    let mut harness = crate::testing::Harness::new(false).with_label("flag");
+    assert!(harness.click("flag"));
+    assert!(harness.value);
+    assert!(harness.widget_tree().contains("CheckBox \"flag\" [x]"));
+
+    let mut harness = crate::testing::Harness::new("abc".to_string()).with_label("name");
+    assert!(harness.type_text("name", "xyz"));
+    assert_eq!(harness.value, "xyz");
+}
+
+#[test]
+fn text_edits_are_filtered_and_flagged() {
+    use crate::options::Charset;
+
+    let options = InspectOptions {
+        charset: Some(Charset::Hex),
+        max_len: Some(6),
+        regex: Some("[0-9]+".to_string()),
+        ..Default::default()
+    };
+    let mut harness = crate::testing::Harness::new(String::new())
+        .with_label("id")
+        .with_options(options);
+    assert!(harness.type_text("id", "1x2\nbeef99"));
+    assert_eq!(harness.value, "12beef");
+    assert!(harness.widget_tree().contains("does not match"));
+    assert!(harness.type_text("id", "1234"));
+    assert!(!harness.widget_tree().contains("does not match"));
+}
+
+#[test]
+fn vec_elements_are_pushed_and_removed() {
+    let mut harness = crate::testing::Harness::new(vec![1u8]).with_label("v");
+    assert!(harness.query("Push default").is_none(), "collapsed");
+    harness.click("v");
+    assert!(harness.click("Push default"));
+    assert_eq!(harness.value, vec![1, 0]);
+    harness.click("⋯");
+    assert!(harness.click("Remove"));
+    assert_eq!(harness.value, vec![0]);
+}
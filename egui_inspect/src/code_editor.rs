@@ -0,0 +1,235 @@
+//! Editor for source code held in `String` fields annotated with `#[inspect(code = "...")]`: a
+//! monospace multiline edit with line numbers, where Tab indents, and a find box (toggled by the
+//! 🔍 button, or Ctrl+F while editing).
+//!
+//! With the `syntax_highlighting` feature, the code is highlighted by egui_extras' built-in
+//! highlighter, which knows `"rust"`, `"toml"`, `"python"` and C-like languages (`"c"`, `"cpp"`,
+//! and the shading languages `"glsl"`, `"hlsl"` and `"wgsl"`, highlighted as C).
+//!
+//! ```
+//! # use egui_inspect::*;
+//! #[derive(EguiInspect)]
+//! struct Material {
+//!     #[inspect(code = "glsl")]
+//!     fragment_shader: String,
+//! }
+//! ```
+
+use egui::text::{CCursor, CCursorRange, LayoutJob};
+use egui::text_edit::TextEditState;
+use egui::{Key, Modifiers, Response, TextEdit, TextStyle, Ui};
+
+use crate::help::field_label;
+use crate::widget_state::with_state;
+
+/// State of the find box of an editor
+#[derive(Clone, Default)]
+struct Find {
+    open: bool,
+    query: String,
+    /// Index of the match selected last, reset as the query changes
+    current: Option<usize>,
+}
+
+/// Code editor (see [self]) for `code` written in `language`
+pub fn code_edit(code: &mut String, label: &str, ui: &mut Ui, language: &str) -> Response {
+    let edit_id = ui.id().with("code_edit");
+    ui.vertical(|ui| {
+        let find_id = ui.id().with("code_find");
+        let label = with_state(ui, find_id, |find: &mut Find, ui| {
+            let label = ui
+                .horizontal(|ui| {
+                    let label = (!label.is_empty()).then(|| field_label(ui, label, ":"));
+                    let toggle = ui
+                        .selectable_label(find.open, "🔍")
+                        .on_hover_text("Find (Ctrl+F)");
+                    if toggle.clicked() {
+                        find.open = !find.open;
+                    }
+                    label
+                })
+                .inner;
+            let editing = ui.memory(|memory| memory.has_focus(edit_id));
+            let shortcut = editing && ui.input_mut(|i| i.consume_key(Modifiers::COMMAND, Key::F));
+            if shortcut {
+                find.open = true;
+            }
+            if find.open {
+                find_box(find, code, edit_id, shortcut, ui);
+            }
+            label
+        });
+
+        let lines = code.split('\n').count();
+        ui.horizontal_top(|ui| {
+            line_numbers(lines, ui);
+            let mut layouter = |ui: &Ui, text: &dyn egui::TextBuffer, _wrap_width: f32| {
+                // NOTE: lines are not wrapped, so that they stay next to their numbers
+                let job = highlight(ui, text.as_str(), language);
+                ui.fonts_mut(|fonts| fonts.layout_job(job))
+            };
+            egui::ScrollArea::horizontal()
+                .id_salt(edit_id)
+                .show(ui, |ui| {
+                    let response = TextEdit::multiline(code)
+                        .id(edit_id)
+                        .code_editor()
+                        .desired_rows(lines.max(4))
+                        .desired_width(f32::INFINITY)
+                        .layouter(&mut layouter)
+                        .show(ui)
+                        .response;
+                    match label {
+                        Some(label) => response.labelled_by(label.id),
+                        None => response,
+                    }
+                })
+                .inner
+        })
+        .inner
+    })
+    .inner
+}
+
+/// Read-only view of `code` written in `language`, with line numbers
+pub fn code_view(code: &str, label: &str, ui: &mut Ui, language: &str) {
+    ui.vertical(|ui| {
+        if !label.is_empty() {
+            field_label(ui, label, ":");
+        }
+        ui.horizontal_top(|ui| {
+            line_numbers(code.split('\n').count(), ui);
+            let job = highlight(ui, code, language);
+            ui.add(egui::Label::new(job).selectable(true).extend());
+        });
+    });
+}
+
+/// Query field, match count and the buttons selecting the next (or previous) match in the editor
+fn find_box(find: &mut Find, code: &str, edit_id: egui::Id, focus: bool, ui: &mut Ui) {
+    ui.horizontal(|ui| {
+        ui.label("Find:");
+        let query = ui.add(TextEdit::singleline(&mut find.query).desired_width(120.0));
+        if focus {
+            query.request_focus();
+        }
+        if query.changed() {
+            find.current = None;
+        }
+        // char offsets of the matches, as used by text cursors
+        let matches: Vec<usize> = match find.query.is_empty() {
+            true => vec![],
+            false => code
+                .match_indices(find.query.as_str())
+                .map(|(start, _)| code[..start].chars().count())
+                .collect(),
+        };
+        ui.label(format!("{} matches", matches.len()));
+
+        // NOTE: consumed, as the editor would otherwise insert it in place of the match it gets
+        // focused on
+        let entered =
+            query.lost_focus() && ui.input_mut(|i| i.consume_key(Modifiers::NONE, Key::Enter));
+        let previous = ui
+            .small_button("⏶")
+            .on_hover_text("Previous match")
+            .clicked();
+        let next = ui.small_button("⏷").on_hover_text("Next match").clicked() || entered;
+        let n = matches.len();
+        let current = match (previous, next, find.current) {
+            _ if n == 0 => None,
+            (true, _, Some(current)) => Some((current + n - 1) % n),
+            (true, _, None) => Some(n - 1),
+            (_, true, Some(current)) => Some((current + 1) % n),
+            (_, true, None) => Some(0),
+            _ => None,
+        };
+        if let Some(current) = current {
+            find.current = Some(current);
+            let start = matches[current];
+            let end = start + find.query.chars().count();
+            let mut state = TextEditState::load(ui.ctx(), edit_id).unwrap_or_default();
+            let selection = CCursorRange::two(CCursor::new(start), CCursor::new(end));
+            state.cursor.set_char_range(Some(selection));
+            state.store(ui.ctx(), edit_id);
+            ui.memory_mut(|memory| memory.request_focus(edit_id));
+        }
+    });
+}
+
+/// Column of the numbers of the `lines` of code, aligned with those of the editor
+fn line_numbers(lines: usize, ui: &mut Ui) {
+    let numbers = (1..=lines)
+        .map(|i| i.to_string())
+        .collect::<Vec<_>>()
+        .join("\n");
+    let font = TextStyle::Monospace.resolve(ui.style());
+    let width =
+        ui.fonts_mut(|fonts| fonts.glyph_width(&font, '0')) * lines.to_string().len() as f32;
+    ui.add(
+        TextEdit::multiline(&mut numbers.as_str())
+            .code_editor()
+            .interactive(false)
+            .frame(false)
+            .desired_width(width)
+            .desired_rows(1),
+    );
+}
+
+/// Layout of `code`, highlighted as `language` with the `syntax_highlighting` feature
+pub(crate) fn highlight(ui: &Ui, code: &str, language: &str) -> LayoutJob {
+    #[cfg(feature = "syntax_highlighting")]
+    {
+        let theme = egui_extras::syntax_highlighting::CodeTheme::from_memory(ui.ctx(), ui.style());
+        egui_extras::syntax_highlighting::highlight(
+            ui.ctx(),
+            ui.style(),
+            &theme,
+            code,
+            highlighted_as(language),
+        )
+    }
+    #[cfg(not(feature = "syntax_highlighting"))]
+    {
+        let _ = language;
+        let font = TextStyle::Monospace.resolve(ui.style());
+        LayoutJob::simple(
+            code.to_string(),
+            font,
+            ui.visuals().text_color(),
+            f32::INFINITY,
+        )
+    }
+}
+
+/// Language known to egui_extras' highlighter that `language` is highlighted as
+#[cfg(feature = "syntax_highlighting")]
+fn highlighted_as(language: &str) -> &str {
+    match language.to_lowercase().as_str() {
+        "glsl" | "hlsl" | "wgsl" | "frag" | "vert" | "shader" => "c",
+        _ => language,
+    }
+}
+
+#[test]
+fn code_is_numbered_and_searchable() {
+    let options = crate::InspectOptions {
+        code: Some("rust".to_string()),
+        ..Default::default()
+    };
+    let code = "fn main() {\n    let x = 1;\n    x\n}".to_string();
+    let mut harness = crate::testing::Harness::new(code)
+        .with_label("source")
+        .with_options(options);
+    assert!(harness
+        .widget_tree()
+        .contains(r#"MultilineTextInput = "1\n2\n3\n4""#));
+
+    harness.click("🔍");
+    assert!(!harness.type_text("Find", "x"));
+    assert!(harness.widget_tree().contains("2 matches"));
+    // the first match is selected in the editor, and replaced by what is typed
+    assert!(harness.input(vec![vec![egui::Event::Text("y".to_string())]]));
+    assert_eq!(harness.value, "fn main() {\n    let y = 1;\n    x\n}");
+    assert!(harness.widget_tree().contains("1 matches"));
+}
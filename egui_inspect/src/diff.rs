@@ -0,0 +1,131 @@
+//! Helpers for [crate::EguiInspect::inspect_diff], which draws two values side by side in columns
+//! and highlights where they differ.
+//!
+//! ```
+//! # use egui_inspect::*;
+//! #[derive(EguiInspect, Default)]
+//! struct Config {
+//!     iters: usize,
+//!     names: Vec<String>,
+//! }
+//!
+//! fn compare(before: &Config, after: &Config, ui: &mut egui::Ui) {
+//!     if !before.inspect_diff(after, "config", ui) {
+//!         ui.label("no changes");
+//!     }
+//! }
+//! ```
+
+use egui::{epaint::RectShape, Color32, CornerRadius, Shape, Ui, Visuals};
+
+use crate::EguiInspect;
+
+/// How a row of a diff view differs between the left and right value
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DiffKind {
+    Same,
+    Changed,
+    /// only present in the right value
+    Added,
+    /// only present in the left value
+    Removed,
+}
+
+impl DiffKind {
+    pub fn from_differs(differs: bool) -> Self {
+        match differs {
+            true => DiffKind::Changed,
+            false => DiffKind::Same,
+        }
+    }
+
+    fn background(self, visuals: &Visuals) -> Option<Color32> {
+        let color = match self {
+            DiffKind::Same => return None,
+            DiffKind::Changed => visuals.warn_fg_color,
+            DiffKind::Added => Color32::GREEN,
+            DiffKind::Removed => visuals.error_fg_color,
+        };
+        Some(color.gamma_multiply(0.15))
+    }
+}
+
+/// Draws `left` and `right` in two columns, over a background showing the `kind` of difference
+pub fn diff_columns(
+    ui: &mut Ui,
+    kind: DiffKind,
+    left: impl FnOnce(&mut Ui),
+    right: impl FnOnce(&mut Ui),
+) {
+    let background = ui.painter().add(Shape::Noop);
+    let rect = ui
+        .scope(|ui| {
+            ui.columns(2, |cols| {
+                left(&mut cols[0]);
+                right(&mut cols[1]);
+            })
+        })
+        .response
+        .rect;
+    if let Some(color) = kind.background(ui.visuals()) {
+        ui.painter().set(
+            background,
+            RectShape::filled(rect.expand(1.0), CornerRadius::same(2), color),
+        );
+    }
+}
+
+/// Diff of values compared as a whole, e.g. numbers or strings, returns whether they differ
+pub fn diff_leaf<T: EguiInspect + PartialEq>(
+    left: &T,
+    right: &T,
+    label: &str,
+    ui: &mut Ui,
+) -> bool {
+    let differs = left != right;
+    diff_columns(
+        ui,
+        DiffKind::from_differs(differs),
+        |ui| left.inspect(label, ui),
+        |ui| right.inspect(label, ui),
+    );
+    differs
+}
+
+/// Diff row of an entry only present on one side (e.g. an element missing from the other `Vec`)
+pub fn diff_one_sided<T: EguiInspect>(value: &T, added: bool, label: &str, ui: &mut Ui) {
+    let missing = |ui: &mut Ui| {
+        ui.weak(format!("{label}: <none>"));
+    };
+    match added {
+        true => diff_columns(ui, DiffKind::Added, missing, |ui| value.inspect(label, ui)),
+        false => diff_columns(
+            ui,
+            DiffKind::Removed,
+            |ui| value.inspect(label, ui),
+            missing,
+        ),
+    }
+}
+
+/// Labels a group of nested diffs (e.g. struct fields), indenting them if there is a label
+pub fn diff_group(label: &str, ui: &mut Ui, diff_items: impl FnOnce(&mut Ui) -> bool) -> bool {
+    if label.is_empty() {
+        return diff_items(ui);
+    }
+    ui.strong(label);
+    ui.indent(label, diff_items).inner
+}
+
+#[test]
+fn leaf_diff_detects_changes() {
+    let ctx = egui::Context::default();
+    let _ = ctx.run(Default::default(), |ctx| {
+        egui::CentralPanel::default().show(ctx, |ui| {
+            assert!(diff_leaf(&1u32, &2, "x", ui));
+            assert!(!diff_leaf(&"a".to_string(), &"a".to_string(), "s", ui));
+            assert!(vec![1u32, 2].inspect_diff(&vec![1, 2, 3], "v", ui));
+            assert!(!Some(1.0f32).inspect_diff(&Some(1.0), "o", ui));
+        });
+    });
+}
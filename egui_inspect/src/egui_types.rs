@@ -1,13 +1,23 @@
+use egui::Response;
+
+use crate::diff::diff_leaf;
+use crate::help::field_label;
+
 impl crate::EguiInspect for egui::Color32 {
     fn inspect(&self, label: &str, ui: &mut egui::Ui) {
         ui.label(format!("{label}: {:?}", self));
     }
 
-    fn inspect_mut(&mut self, label: &str, ui: &mut egui::Ui) {
+    fn inspect_mut(&mut self, label: &str, ui: &mut egui::Ui) -> Response {
         ui.horizontal(|ui| {
-            ui.label(label);
-            ui.color_edit_button_srgba(self);
-        });
+            field_label(ui, label, "");
+            ui.color_edit_button_srgba(self)
+        })
+        .inner
+    }
+
+    fn inspect_diff(&self, other: &Self, label: &str, ui: &mut egui::Ui) -> bool {
+        diff_leaf(self, other, label, ui)
     }
 }
 
@@ -16,28 +26,37 @@ impl crate::EguiInspect for egui::Stroke {
         ui.label(format!("{label}: {:?}", self));
     }
 
-    fn inspect_mut(&mut self, label: &str, ui: &mut egui::Ui) {
+    fn inspect_mut(&mut self, label: &str, ui: &mut egui::Ui) -> Response {
         ui.horizontal(|ui| {
-            ui.label(format!("{label}: "));
-            ui.add(self);
-        });
+            field_label(ui, label, ": ");
+            ui.add(self)
+        })
+        .inner
+    }
+
+    fn inspect_diff(&self, other: &Self, label: &str, ui: &mut egui::Ui) -> bool {
+        diff_leaf(self, other, label, ui)
     }
 }
 
 impl crate::EguiInspect for egui::Vec2 {
     fn inspect(&self, label: &str, ui: &mut egui::Ui) {
         ui.horizontal(|ui| {
-            ui.label(label);
+            field_label(ui, label, "");
             self.x.inspect("x", ui);
             self.y.inspect("y", ui);
         });
     }
 
-    fn inspect_mut(&mut self, label: &str, ui: &mut egui::Ui) {
+    fn inspect_mut(&mut self, label: &str, ui: &mut egui::Ui) -> Response {
         ui.horizontal(|ui| {
-            ui.label(label);
-            self.x.inspect_mut("x", ui);
-            self.y.inspect_mut("y", ui);
-        });
+            field_label(ui, label, "");
+            self.x.inspect_mut("x", ui) | self.y.inspect_mut("y", ui)
+        })
+        .inner
+    }
+
+    fn inspect_diff(&self, other: &Self, label: &str, ui: &mut egui::Ui) -> bool {
+        diff_leaf(self, other, label, ui)
     }
 }
@@ -0,0 +1,266 @@
+//! Filtering the fields of (large, nested) derived structs by label, through a search box drawn by
+//! the [Searchable] wrapper.
+//!
+//! While a query is entered, fields whose label (fuzzy) matches it are drawn in full, with the
+//! matched characters highlighted, fields containing such a match (see
+//! [crate::EguiInspect::matches_filter]) are drawn filtered in turn, with their collapsing headers
+//! forced open, and all other fields are hidden.
+//!
+//! ```
+//! # use egui_inspect::*;
+//! # use egui_inspect::filter::Searchable;
+//! #[derive(EguiInspect, Default)]
+//! struct Solver {
+//!     tolerance: f64,
+//!     max_iters: usize,
+//! }
+//!
+//! #[derive(EguiInspect, Default)]
+//! #[inspect(collapsible)]
+//! struct SimConfig {
+//!     time_step: f64,
+//!     solver: Solver,
+//! }
+//!
+//! // typing "iter" only shows `solver` (opened) and its `max_iters`
+//! let config = Searchable::new(SimConfig::default());
+//! ```
+
+use std::ops::{Deref, DerefMut};
+
+use egui::{Id, Response, Ui, WidgetText};
+
+use crate::search_select::{non_contiguous_highlight, FuzzySearch, SearchMethod};
+use crate::validation::ValidationError;
+use crate::widget_state::with_state;
+use crate::{EguiInspect, InspectOptions};
+
+/// The filter applied to the values drawn within [with_filter]
+#[derive(Clone, Debug, Default)]
+pub struct ActiveFilter {
+    pub query: String,
+    /// set within fields whose label matched, whose contents are then all shown
+    pub show_all: bool,
+}
+
+fn filter_id() -> Id {
+    Id::new("egui_inspect_active_filter")
+}
+
+/// The filter set by the enclosing [with_filter] (`None` outside of one, or for an empty query)
+pub fn active_filter(ui: &Ui) -> Option<ActiveFilter> {
+    ui.data(|d| d.get_temp::<ActiveFilter>(filter_id()))
+        .filter(|filter| !filter.query.is_empty())
+}
+
+/// Draws with `filter` as the active filter, restoring the previous one afterwards
+pub fn with_filter<R>(
+    ui: &mut Ui,
+    filter: Option<ActiveFilter>,
+    f: impl FnOnce(&mut Ui) -> R,
+) -> R {
+    let previous = ui.data(|d| d.get_temp::<ActiveFilter>(filter_id()));
+    set_filter(ui, filter);
+    let ret = f(ui);
+    set_filter(ui, previous);
+    ret
+}
+
+fn set_filter(ui: &Ui, filter: Option<ActiveFilter>) {
+    ui.data_mut(|d| match filter {
+        Some(filter) => d.insert_temp(filter_id(), filter),
+        None => {
+            d.remove_temp::<ActiveFilter>(filter_id());
+        }
+    });
+}
+
+/// Indices of the characters of `label` matched by `query`
+pub fn label_match(label: &str, query: &str) -> Option<Vec<usize>> {
+    FuzzySearch::match_idxs(label, query).map(|(_, char_idxs)| char_idxs)
+}
+
+/// Whether a field labelled `label` matches `query`
+pub fn label_matches(label: &str, query: &str) -> bool {
+    !label.is_empty() && label_match(label, query).is_some()
+}
+
+/// Whether the active filter searches within `value`, and something nested in it matches
+pub fn contains_match<T: EguiInspect + ?Sized>(ui: &Ui, value: &T) -> bool {
+    active_filter(ui).is_some_and(|filter| !filter.show_all && value.matches_filter(&filter.query))
+}
+
+/// Whether the label of a field which is not [EguiInspect] matches the active filter
+pub fn label_matches_active(ui: &Ui, label: &str) -> bool {
+    active_filter(ui).is_some_and(|filter| label_matches(label, &filter.query))
+}
+
+/// Draws a field labelled `label` unless the active filter hides it, in which case `None` is
+/// returned. `contains_match` is whether something nested in the field matches (see
+/// [contains_match]), if instead its label matches, all its contents are shown.
+pub fn show_field<R>(
+    ui: &mut Ui,
+    label: &str,
+    contains_match: bool,
+    inspect: impl FnOnce(&mut Ui) -> R,
+) -> Option<R> {
+    let Some(filter) = active_filter(ui) else {
+        return Some(inspect(ui));
+    };
+    if filter.show_all {
+        Some(inspect(ui))
+    } else if label_matches(label, &filter.query) {
+        let filter = ActiveFilter {
+            show_all: true,
+            ..filter
+        };
+        Some(with_filter(ui, Some(filter), inspect))
+    } else if contains_match {
+        Some(inspect(ui))
+    } else {
+        None
+    }
+}
+
+/// Forces collapsing headers open while the active filter matches something nested in `value`
+pub fn force_open<T: EguiInspect + ?Sized>(ui: &Ui, value: &T) -> Option<bool> {
+    contains_match(ui, value).then_some(true)
+}
+
+/// `label` followed by `suffix`, with the characters matched by the active filter highlighted
+pub fn highlighted_label(ui: &Ui, label: &str, suffix: &str) -> WidgetText {
+    let char_idxs = active_filter(ui).and_then(|filter| label_match(label, &filter.query));
+    match char_idxs {
+        Some(char_idxs) => non_contiguous_highlight(
+            &format!("{label}{suffix}"),
+            &char_idxs,
+            ui.visuals().warn_fg_color,
+            ui.visuals().text_color(),
+        )
+        .into(),
+        None => format!("{label}{suffix}").into(),
+    }
+}
+
+/// Wraps a value to draw a search box above it, filtering its fields by label (see [self])
+pub struct Searchable<T: EguiInspect> {
+    value: T,
+}
+
+impl<T: EguiInspect> Searchable<T> {
+    pub fn new(value: T) -> Self {
+        Self { value }
+    }
+
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+
+    /// Draws the search box, returning the current query
+    fn search_box(&self, ui: &mut Ui) -> String {
+        let query_id = ui.id().with("filter_query");
+        with_state(ui, query_id, |query: &mut String, ui| {
+            ui.horizontal(|ui| {
+                ui.label("🔍");
+                ui.add(egui::TextEdit::singleline(query).hint_text("Filter fields"));
+                if !query.is_empty() && ui.small_button("✖").clicked() {
+                    query.clear();
+                }
+            });
+            if !query.is_empty() && !self.value.matches_filter(query) {
+                ui.weak("No matching fields");
+            }
+            query.clone()
+        })
+    }
+}
+
+impl<T: EguiInspect + Default> Default for Searchable<T> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl<T: EguiInspect> Deref for Searchable<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T: EguiInspect> DerefMut for Searchable<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.value
+    }
+}
+
+impl<T: EguiInspect> EguiInspect for Searchable<T> {
+    fn inspect(&self, label: &str, ui: &mut Ui) {
+        self.inspect_with(label, ui, &InspectOptions::default());
+    }
+
+    fn inspect_with(&self, label: &str, ui: &mut Ui, options: &InspectOptions) {
+        let query = self.search_box(ui);
+        let filter = ActiveFilter {
+            query,
+            show_all: false,
+        };
+        with_filter(ui, Some(filter), |ui| self.value.inspect_with(label, ui, options));
+    }
+
+    fn inspect_mut(&mut self, label: &str, ui: &mut Ui) -> Response {
+        self.inspect_mut_with(label, ui, &InspectOptions::default())
+    }
+
+    fn inspect_mut_with(&mut self, label: &str, ui: &mut Ui, options: &InspectOptions) -> Response {
+        let query = self.search_box(ui);
+        let filter = ActiveFilter {
+            query,
+            show_all: false,
+        };
+        with_filter(ui, Some(filter), |ui| {
+            self.value.inspect_mut_with(label, ui, options)
+        })
+    }
+
+    fn is_inline(&self) -> bool {
+        false
+    }
+
+    fn matches_filter(&self, query: &str) -> bool {
+        self.value.matches_filter(query)
+    }
+
+    fn validation_errors(&self) -> Vec<ValidationError> {
+        self.value.validation_errors()
+    }
+
+    fn inspect_diff(&self, other: &Self, label: &str, ui: &mut Ui) -> bool {
+        self.value.inspect_diff(&other.value, label, ui)
+    }
+}
+
+#[test]
+fn fields_are_hidden_unless_they_match() {
+    let ctx = egui::Context::default();
+    let _ = ctx.run(Default::default(), |ctx| {
+        egui::CentralPanel::default().show(ctx, |ui| {
+            let filter = ActiveFilter {
+                query: "iter".to_string(),
+                show_all: false,
+            };
+            with_filter(ui, Some(filter), |ui| {
+                assert!(show_field(ui, "max_iters", false, |_| ()).is_some());
+                assert!(show_field(ui, "tolerance", false, |_| ()).is_none());
+                assert!(show_field(ui, "solver", true, |_| ()).is_some());
+                // contents of a matched field are all shown
+                show_field(ui, "iterations", false, |ui| {
+                    assert!(show_field(ui, "tolerance", false, |_| ()).is_some());
+                });
+            });
+            assert!(active_filter(ui).is_none());
+            assert!(show_field(ui, "tolerance", false, |_| ()).is_some());
+        });
+    });
+}
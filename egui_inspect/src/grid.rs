@@ -0,0 +1,132 @@
+//! Two column (label | value) layout of derived structs, enabled per struct with
+//! `#[inspect(grid)]`, or at runtime for all derived structs without a `grid` attribute with
+//! [set_grid_by_default] (which may be opted out of with `#[inspect(grid = false)]`).
+//!
+//! Consecutive fields which fit on a single line (see [crate::EguiInspect::is_inline]) are drawn in
+//! a striped [egui::Grid], while nested structs and collections span the whole width between them.
+
+use egui::{Context, Grid, Id, Response, TextStyle, TextWrapMode, Ui, WidgetText};
+
+use crate::filter::{highlighted_label, show_field};
+use crate::help::show_doc;
+use crate::utils::empty_response;
+
+fn grid_by_default_id() -> Id {
+    Id::new("egui_inspect::grid_by_default")
+}
+
+/// Lays out the derived structs drawn in `ctx` which have no `grid` attribute in a grid (or not)
+pub fn set_grid_by_default(ctx: &Context, grid: bool) {
+    ctx.data_mut(|d| d.insert_temp(grid_by_default_id(), grid));
+}
+
+/// Whether derived structs without a `grid` attribute are laid out in a grid (see
+/// [set_grid_by_default]), `false` unless set
+pub fn grid_by_default(ui: &Ui) -> bool {
+    ui.data(|d| d.get_temp(grid_by_default_id()))
+        .unwrap_or(false)
+}
+
+/// Draws a value, given the label to use (empty for inline rows)
+pub type InspectFn<'a> = Box<dyn FnOnce(&mut Ui, &str) -> Response + 'a>;
+
+pub struct GridRow<'a> {
+    pub label: &'a str,
+    /// whether the value is drawn in the value column, rather than spanning the whole row
+    pub inline: bool,
+    /// whether the active field filter matches something nested in the value (see [crate::filter])
+    pub contains_match: bool,
+    /// shown when hovering the label
+    pub doc: Option<&'a str>,
+    pub inspect: InspectFn<'a>,
+}
+
+/// Draws the rows, returning the union of their responses
+pub fn grid_rows(ui: &mut Ui, rows: Vec<GridRow>) -> Response {
+    // all grids share the width of the label column, so that they line up, while the value
+    // column fits its widgets
+    let label_width = rows
+        .iter()
+        .filter(|row| row.inline)
+        .map(|row| text_width(ui, row.label))
+        .fold(0.0, f32::max);
+
+    let mut response = empty_response(ui);
+    let mut rows = rows.into_iter().peekable();
+    let mut grid_index = 0;
+    while let Some(row) = rows.next() {
+        if !row.inline {
+            let (label, contains_match) = (row.label, row.contains_match);
+            if let Some(row_response) =
+                show_field(ui, label, contains_match, |ui| (row.inspect)(ui, label))
+            {
+                response |= row_response;
+            }
+            continue;
+        }
+        let mut inline_rows = vec![row];
+        while let Some(row) = rows.next_if(|row| row.inline) {
+            inline_rows.push(row);
+        }
+        response |= Grid::new(("inspect_grid", grid_index))
+            .num_columns(2)
+            .striped(true)
+            .show(ui, |ui| {
+                let mut response = empty_response(ui);
+                for row in inline_rows {
+                    let (label, contains_match) = (row.label, row.contains_match);
+                    show_field(ui, label, contains_match, |ui| {
+                        let label_response = ui
+                            .scope(|ui| {
+                                ui.set_min_width(label_width);
+                                ui.label(highlighted_label(ui, label, ""))
+                            })
+                            .inner;
+                        show_doc(label_response, row.doc);
+                        response |= (row.inspect)(ui, "");
+                        ui.end_row();
+                    });
+                }
+                response
+            })
+            .inner;
+        grid_index += 1;
+    }
+    response
+}
+
+fn text_width(ui: &Ui, text: &str) -> f32 {
+    WidgetText::from(text)
+        .into_galley(
+            ui,
+            Some(TextWrapMode::Extend),
+            f32::INFINITY,
+            TextStyle::Body,
+        )
+        .size()
+        .x
+}
+
+#[test]
+fn grid_is_enabled_at_runtime() {
+    use crate as egui_inspect;
+    use crate::testing::Harness;
+    use egui_inspect_derive::EguiInspect;
+
+    #[derive(EguiInspect, Default)]
+    struct Params {
+        a_long_parameter_name: u8,
+        n: u8,
+    }
+
+    let mut harness = Harness::new(Params::default());
+    let (long, short) = (harness.get("a_long_parameter_name").rect, harness.get("n").rect);
+    assert!(long.min.x > short.min.x);
+
+    set_grid_by_default(harness.ctx(), true);
+    harness.run();
+    let (long, short) = (harness.get("a_long_parameter_name").rect, harness.get("n").rect);
+    assert_eq!(long.min.x, short.min.x);
+    // the value column is as wide as the values, rather than the labels
+    assert!(short.width() < long.min.x);
+}
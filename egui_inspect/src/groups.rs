@@ -0,0 +1,108 @@
+//! Grouping the fields of derived structs (or enum variants) annotated with
+//! `#[inspect(group = "...")]` under a heading, a collapsing header or a tab, as chosen by the
+//! `groups` attribute of the struct.
+//!
+//! ```
+//! # use egui_inspect::*;
+//! #[derive(EguiInspect, Default)]
+//! #[inspect(groups = "tabs")]
+//! struct Solver {
+//!     tolerance: f64,
+//!     #[inspect(group = "Advanced")]
+//!     relaxation: f64,
+//!     #[inspect(group = "Output")]
+//!     verbose: bool,
+//!     #[inspect(group = "Advanced", order = -1)]
+//!     max_iters: usize,
+//! }
+//! ```
+//!
+//! Fields of the same group are drawn together regardless of their declaration order, the groups
+//! being placed at their first field. Fields may be reordered with `order` (`0` by default, ties
+//! keep the declaration order). Tabs are drawn in a single tab bar, below all ungrouped fields.
+//!
+//! While a field filter is active (see [crate::filter]), groups are hidden unless their name or
+//! one of their fields matches, and the first tab with a match is selected.
+
+use egui::{CollapsingHeader, CollapsingResponse, Ui};
+
+use crate::filter::{active_filter, highlighted_label, show_field};
+use crate::widget_state::with_state;
+
+/// Draws a group below a heading, unless the active filter hides it. `contains_match` is whether
+/// the filter matches one of its fields.
+pub fn section<R>(
+    ui: &mut Ui,
+    name: &str,
+    contains_match: bool,
+    body: impl FnOnce(&mut Ui) -> R,
+) -> Option<R> {
+    show_field(ui, name, contains_match, |ui| {
+        ui.push_id(name, |ui| {
+            ui.label(highlighted_label(ui, name, "").strong());
+            ui.separator();
+            body(ui)
+        })
+        .inner
+    })
+}
+
+/// Draws a group within a collapsing header (forced open while the active filter matches one of
+/// its fields), unless the active filter hides it
+pub fn collapsing<R>(
+    ui: &mut Ui,
+    name: &str,
+    contains_match: bool,
+    body: impl FnOnce(&mut Ui) -> R,
+) -> Option<CollapsingResponse<R>> {
+    show_field(ui, name, contains_match, |ui| {
+        CollapsingHeader::new(highlighted_label(ui, name, ""))
+            .id_salt(name)
+            .open(contains_match.then_some(true))
+            .show(ui, body)
+    })
+}
+
+/// Draws a tab bar for the groups `names`, returning the index of the selected one. While the
+/// active filter matches nothing in the selected group, the first group with a match (as given by
+/// `contains_match`) is selected instead.
+pub fn tabs(ui: &mut Ui, names: &[&str], contains_match: &[bool]) -> usize {
+    let id = ui.id().with("inspect_group_tab");
+    with_state(ui, id, |selected: &mut usize, ui| {
+        *selected = (*selected).min(names.len().saturating_sub(1));
+        if active_filter(ui).is_some() && !contains_match.get(*selected).copied().unwrap_or(true) {
+            if let Some(first) = contains_match.iter().position(|m| *m) {
+                *selected = first;
+            }
+        }
+        ui.horizontal_wrapped(|ui| {
+            for (i, name) in names.iter().enumerate() {
+                ui.selectable_value(selected, i, highlighted_label(ui, name, ""));
+            }
+        });
+        ui.separator();
+        *selected
+    })
+}
+
+#[test]
+fn groups_are_hidden_unless_they_match() {
+    use crate::filter::{with_filter, ActiveFilter};
+
+    let ctx = egui::Context::default();
+    let _ = ctx.run(Default::default(), |ctx| {
+        egui::CentralPanel::default().show(ctx, |ui| {
+            assert!(section(ui, "Advanced", false, |_| ()).is_some());
+            let filter = ActiveFilter {
+                query: "iter".to_string(),
+                show_all: false,
+            };
+            with_filter(ui, Some(filter), |ui| {
+                assert!(section(ui, "Advanced", false, |_| ()).is_none());
+                assert!(collapsing(ui, "Advanced", true, |_| ())
+                    .is_some_and(|collapsing| collapsing.body_returned.is_some()));
+                assert_eq!(tabs(ui, &["Output", "Advanced"], &[false, true]), 1);
+            });
+        });
+    });
+}
@@ -0,0 +1,177 @@
+//! Doc comments of derived structs, fields and enum variants, shown as hover text on their labels
+//! and, for the field last focused (or hovered), in an optional help panel.
+//!
+//! ```
+//! # use egui_inspect::*;
+//! #[derive(EguiInspect, Default)]
+//! struct Solver {
+//!     /// Residual below which the solver stops
+//!     tolerance: f64,
+//! }
+//!
+//! fn ui(solver: &mut Solver, ctx: &egui::Context) {
+//!     egui_inspect::help::help_side_panel(ctx);
+//!     egui::CentralPanel::default().show(ctx, |ui| solver.inspect_mut("solver", ui));
+//! }
+//! ```
+//!
+//! The doc of a field is handed to the first label drawn by its inspect impl, manual impls can
+//! claim it with [field_label] or [attach_doc] (it is otherwise shown over the whole field).
+
+use egui::{Context, Id, Response, SidePanel, Ui};
+
+use crate::filter::highlighted_label;
+
+fn pending_id() -> Id {
+    Id::new("egui_inspect_pending_doc")
+}
+
+fn help_id() -> Id {
+    Id::new("egui_inspect_help")
+}
+
+/// The field shown in the help panel
+#[derive(Clone, Default)]
+struct HelpState {
+    label: String,
+    doc: &'static str,
+    /// whether the field contained the focused widget, rather than just the pointer
+    focused: bool,
+    pass_nr: u64,
+}
+
+fn set_pending(ui: &Ui, doc: Option<&'static str>) {
+    ui.data_mut(|d| match doc {
+        Some(doc) => d.insert_temp(pending_id(), doc),
+        None => {
+            d.remove_temp::<&'static str>(pending_id());
+        }
+    });
+}
+
+/// Takes the doc handed to the label about to be drawn (see [with_doc])
+pub fn take_doc(ui: &Ui) -> Option<&'static str> {
+    ui.data_mut(|d| d.remove_temp::<&'static str>(pending_id()))
+}
+
+/// Shows the `doc` (if any) when hovering the response
+pub fn show_doc(response: Response, doc: Option<&str>) -> Response {
+    match doc {
+        Some(doc) => response.on_hover_text(doc),
+        None => response,
+    }
+}
+
+/// Claims the pending doc for a label (or widget) drawn before anything nested in the field
+pub fn attach_doc(ui: &Ui, response: Response) -> Response {
+    show_doc(response, take_doc(ui))
+}
+
+/// Draws the label of a field (highlighted by an active filter, see [crate::filter]), followed by
+/// `suffix`, showing its doc on hover
+pub fn field_label(ui: &mut Ui, label: &str, suffix: &str) -> Response {
+    let response = ui.label(highlighted_label(ui, label, suffix));
+    attach_doc(ui, response)
+}
+
+/// Draws a field documented by `doc`, which is handed to its label, and tracked for the help
+/// panel. If no label claims it, it is shown over the whole field instead.
+pub fn with_doc(
+    ui: &mut Ui,
+    label: &str,
+    doc: &'static str,
+    inspect: impl FnOnce(&mut Ui) -> Response,
+) -> Response {
+    set_pending(ui, Some(doc));
+    let response = inspect(ui);
+    let unclaimed = take_doc(ui).is_some();
+    track_help(ui, label, doc, &response);
+    match unclaimed {
+        true => response.on_hover_text(doc),
+        false => response,
+    }
+}
+
+/// Draws a value of a documented type, whose doc is used unless the field it is drawn for is
+/// documented itself
+pub fn with_type_doc<R>(ui: &mut Ui, doc: &'static str, inspect: impl FnOnce(&mut Ui) -> R) -> R {
+    let pending = ui.data(|d| d.get_temp::<&'static str>(pending_id()));
+    if pending.is_some() {
+        return inspect(ui);
+    }
+    set_pending(ui, Some(doc));
+    let ret = inspect(ui);
+    set_pending(ui, None);
+    ret
+}
+
+/// Records the innermost field containing the focused widget (or else the pointer)
+fn track_help(ui: &Ui, label: &str, doc: &'static str, response: &Response) {
+    let ctx = ui.ctx();
+    let focused = ctx
+        .memory(|m| m.focused())
+        .and_then(|id| ctx.read_response(id))
+        .is_some_and(|focused| response.rect.contains_rect(focused.rect));
+    if !focused && !ui.rect_contains_pointer(response.rect) {
+        return;
+    }
+    let pass_nr = ctx.cumulative_pass_nr();
+    ui.data_mut(|d| {
+        let state = d.get_temp_mut_or_default::<HelpState>(help_id());
+        // inner fields are drawn (and recorded) before the outer ones
+        if state.pass_nr != pass_nr || (focused && !state.focused) {
+            *state = HelpState {
+                label: label.to_string(),
+                doc,
+                focused,
+                pass_nr,
+            };
+        }
+    });
+}
+
+/// Draws the full doc of the field last focused or hovered
+pub fn show_help(ui: &mut Ui) {
+    let state = ui.data(|d| d.get_temp::<HelpState>(help_id()));
+    match state {
+        Some(state) => {
+            ui.strong(state.label);
+            ui.label(state.doc);
+        }
+        None => {
+            ui.weak("Focus or hover a documented field to show its help");
+        }
+    }
+}
+
+/// Right side panel showing [show_help], to be drawn before the central panel
+pub fn help_side_panel(ctx: &Context) {
+    SidePanel::right("egui_inspect_help_panel")
+        .resizable(true)
+        .show(ctx, |ui| {
+            ui.heading("Help");
+            ui.separator();
+            show_help(ui);
+        });
+}
+
+#[test]
+fn doc_goes_to_the_first_label() {
+    let ctx = egui::Context::default();
+    let _ = ctx.run(Default::default(), |ctx| {
+        egui::CentralPanel::default().show(ctx, |ui| {
+            with_doc(ui, "x", "the x", |ui| {
+                assert_eq!(take_doc(ui), Some("the x"));
+                // nested fields get their own doc, or none
+                assert_eq!(take_doc(ui), None);
+                ui.label("x")
+            });
+            assert_eq!(take_doc(ui), None);
+            with_type_doc(ui, "a type", |ui| assert_eq!(take_doc(ui), Some("a type")));
+            with_doc(ui, "y", "the y", |ui| {
+                with_type_doc(ui, "a type", |ui| assert_eq!(take_doc(ui), Some("the y")));
+                ui.label("y")
+            });
+        });
+    });
+}
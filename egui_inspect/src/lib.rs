@@ -15,7 +15,7 @@
 //!     unsigned32: u32,
 //!     #[inspect(hide)]
 //!     skipped: bool,
-//!     #[inspect(custom_func_mut = "custom_bool_inspect")]
+//!     #[inspect(custom_func_mut = custom_bool_inspect)]
 //!     boolean: bool,
 //!     #[inspect(no_edit)]
 //!     raw_string: &'static str,
@@ -25,9 +25,22 @@
 //!     ugly_internal_field_name: u16,
 //! }
 //!
-//! fn custom_bool_inspect(boolean: &mut bool, label: &'static str, ui: &mut egui::Ui) {
+//! fn custom_bool_inspect(boolean: &mut bool, label: &'static str, ui: &mut egui::Ui) -> egui::Response {
 //!    ui.label("C'EST LA GIGA FONCTION CUSTOM WÉ");
-//!    boolean.inspect(label, ui);
+//!    boolean.inspect_mut(label, ui)
+//! }
+//! ```
+//!
+//! `inspect_mut` returns an [egui::Response] aggregated over every widget drawn for the value, so
+//! one can check `.changed()` (or `.hovered()`, ...) instead of comparing against a copy:
+//! ```
+//! # use egui_inspect::*;
+//! # #[derive(EguiInspect, Default)]
+//! # struct Params { gain: f32 }
+//! fn params_ui(params: &mut Params, ui: &mut egui::Ui) {
+//!     if params.inspect_mut("params", ui).changed() {
+//!         println!("params were edited");
+//!     }
 //! }
 //! ```
 //!
@@ -41,28 +54,226 @@
 //! - `hide` *(bool)*: If true, doesn't generate code for the given field
 //! - `no_edit` *(bool)*: If true, never call mut function for the given field (May be overridden by other params)
 //! - `slider` *(bool)*: If true, use a slider when inspecting numbers (`mut` only)
-//! - `min` *(f32)*: Min value for inspecting numbers (`mut` only)
-//! - `max` *(f32)*: Max value for inspecting numbers (`mut` only)
+//! - `log_slider` *(bool)*: If true, use a logarithmic slider when inspecting numbers (`mut` only)
+//! - `min` *(number)*: Min value for inspecting numbers (`mut` only)
+//! - `max` *(number)*: Max value for inspecting numbers (`mut` only), integer bounds are kept
+//!   exactly (e.g. up to `u64::MAX`)
+//! - `step` *(number)*: Numbers snap to multiples of the step, from `min` (`mut` only)
+//! - `speed` *(number)*: Drag speed of numbers, per point (`mut` only)
+//! - `prefix`, `suffix` *(String)*: Text shown around numbers, e.g. a unit (`mut` only)
+//! - `decimals` *(integer)*: Number of decimals shown for numbers (`mut` only)
+//! - `clamp` *(String)*: Whether numbers are clamped to `min..=max` `"always"` (default), on
+//!   `"edits"` only (leaving values already out of range as they are), or `"never"` (`mut` only)
+//! - `format` *(String)*: Display of numbers, `"plain"` (default), `"hex"`, `"binary"`,
+//!   `"scientific"`, `"percent"` (of a fraction) or `"thousands"` (separated), typed in values
+//!   are parsed in the same format (`mut` only)
 //! - `multiline` *(bool)*: If true, display the text on multiple lines (`mut` only)
-//! - `custom_func` *(String)*: Use custom function for non-mut inspect (Evaluate the string as a function path)
-//! - `custom_func_mut` *(String)*: Use custom function for mut inspect (Evaluate the string as a function path)
+//! - `password` *(bool)*: If true, mask the text, e.g. of API tokens
+//! - `hint` *(String)*: Placeholder shown while the text is empty (`mut` only)
+//! - `max_len` *(integer)*: Max number of characters of the text (`mut` only)
+//! - `charset` *(String)*: Characters allowed in the text, `"ascii"`, `"alphanumeric"`,
+//!   `"digits"`, `"hex"`, `"identifier"` or `"file_name"`, others are dropped as they are typed or
+//!   pasted in (`mut` only)
+//! - `regex` *(String)*: Pattern the text should match (see [pattern]), checked when deriving,
+//!   and flagged while the text does not match (`mut` only)
+//! - `code` *(String)*: Edit the text as source code in this language, e.g. `"glsl"` or `"toml"`
+//!   (see [code_editor]), highlighted with the `syntax_highlighting` feature
+//! - `markdown` *(bool)*: If true, render the text as Markdown, edited next to its rendering (see
+//!   [markdown])
+//! - `duplicate` *(bool)*: If true, offer to duplicate the elements of `Vec` and `VecDeque` fields,
+//!   whose elements must be `Clone` (see [vec_editor]) (`mut` only)
+//! - `custom_func` *(path)*: Use custom function for non-mut inspect
+//! - `custom_func_mut` *(path)*: Use custom function for mut inspect,
+//!   the function may return either `()` or an [egui::Response] (see [InspectReturn])
+//! - `validate` *(path)*: Path of a `fn(&T) -> Result<(), String>` checking the field (or, on the
+//!   struct, a `fn(&Self) -> Result<(), String>`), errors are shown inline and collected by
+//!   [EguiInspect::validation_errors] (see [validation])
+//! - `visible_if` *(expression)*: Boolean expression over the sibling fields (in scope by
+//!   reference) or `self`, e.g. `*use_power`, `matches!(mode, Mode::Power)` or
+//!   `self.is_powered()` (tuple fields are reached through `self`, e.g. `self.0 > 1`), the field
+//!   is only drawn (validated and searched) while it holds
+//! - `enabled_if` *(expression)*: Boolean expression as for `visible_if`, the field is drawn
+//!   disabled while it does not hold
+//!
+//! The number and text options in this list reach the field's inspect as an [InspectOptions]
+//! (through [EguiInspect::inspect_mut_with], or [EguiInspect::inspect_with] when drawn read-only),
+//! so they also apply to the elements of containers (e.g. `Vec<f32>` or `Option<String>`).
+//!
+//! `///` doc comments of derived structs, fields and enum variants are shown when hovering their
+//! labels, and in an optional help panel (see [help]).
+//!
+//! Wrapping a value in [filter::Searchable] adds a search box filtering its fields by label.
+//!
+//! With the `testing` feature, inspect UIs can be driven headlessly in tests (see `testing`).
+//!
+//! Structs may be annotated with `#[inspect(grid)]`, to lay out their fields in aligned label and
+//! value columns, which may also be made the default at runtime (see [grid]).
+//!
+//! Fields annotated with `#[inspect(group = "...")]` are drawn together under a heading, collapsing
+//! header or tab (per the struct's `groups` attribute), and may be reordered with `order` (see
+//! [groups]).
+//!
+//! Enum variants take `name`, `hide` (left out of the selector) and `doc` (hover text, defaulting
+//! to the doc comment) attributes, and enums are selected with a combo box, or with radio buttons,
+//! a tab bar or joined buttons when annotated with `radio`, `tabs` or `segmented`. Selecting a
+//! variant needs neither `PartialEq` nor `Default` on the enum: it is constructed by its `ctor`
+//! expression if given (otherwise from the defaults of its fields), and takes over the fields it
+//! shares (by name and type, as written) with the previous variant.
+//!
+//! Fields of tuple structs and variants take the same attributes as named fields (labelled
+//! `Field {i}` and `""` respectively, unless given a `name`). Structs annotated with
+//! `#[inspect(transparent)]` are drawn exactly as their only (non hidden) field, e.g. newtypes.
+//!
+//! Fields annotated with `#[inspect(flatten)]` have their own fields drawn inline with those of the
+//! parent, without the frame and label of the nested struct (see [EguiInspect::inspect_flat]).
+//!
+//! Paths and expressions (including `frame_style`, `ctor` and the `eframe_main` options) are
+//! written as plain Rust, so they are checked, navigated and renamed as any other code. The string
+//! forms used by earlier versions (e.g. `custom_func = "my_func"`) are still accepted.
+//! ```
+//! # use egui_inspect::*;
+//! static WARNING_BOX: FrameStyle = FrameStyle {
+//!     stroke: egui::Stroke { width: 2.0, color: egui::Color32::RED },
+//!     ..DEFAULT_FRAME_STYLE
+//! };
+//!
+//! #[derive(EguiInspect, Default)]
+//! #[inspect(frame_style = WARNING_BOX)]
+//! struct Laser {
+//!     armed: bool,
+//!     #[inspect(enabled_if = *armed, validate = below_limit)]
+//!     power: f32,
+//!     #[inspect(visible_if = "*power > 0.5")]
+//!     cooldown: f32,
+//! }
+//!
+//! fn below_limit(power: &f32) -> Result<(), String> {
+//!     match *power <= 1.0 {
+//!         true => Ok(()),
+//!         false => Err("too powerful".to_string()),
+//!     }
+//! }
+//! ```
+//!
+//! The `init` expression of `eframe_main` may use the [eframe::CreationContext] as `cc`:
+//! ```
+//! # use egui_inspect::*;
+//! #[derive(EguiInspect, EframeMain)]
+//! #[eframe_main(init = Viewer::new(cc))]
+//! struct Viewer {
+//!     zoom: f32,
+//! }
+//!
+//! impl Viewer {
+//!     fn new(cc: &eframe::CreationContext) -> Self {
+//!         Self {
+//!             zoom: cc.egui_ctx.zoom_factor(),
+//!         }
+//!     }
+//! }
+//! ```
 //!
 
 pub use eframe;
 pub use egui;
-use egui::{Frame, Margin, Stroke};
+use egui::{Frame, Margin, Response, Stroke};
 /// See also [EguiInspect]
 pub use egui_inspect_derive::*;
 #[cfg(feature = "plotting")]
 pub use egui_plot;
+pub use options::InspectOptions;
+use validation::ValidationError;
 
 /// Base trait to automatically inspect structs
+///
+/// Widget state (open collapsing headers, selected combo entries, ...) is keyed by the id of the
+/// [egui::Ui] an item is drawn in. The derived and built-in impls give every nested value its own
+/// id scope through [egui::Ui::push_id], salted by field name, index or map key, so the effective
+/// id follows the value's path (e.g. `app.containers.vector[3]`) rather than its label or address.
+/// Manual impls drawing several nested values should do the same.
 pub trait EguiInspect {
     #[allow(unused_variables)]
     fn inspect(&self, label: &str, ui: &mut egui::Ui) {}
-    fn inspect_mut(&mut self, label: &str, ui: &mut egui::Ui) {
+    /// [Self::inspect] with the options set by field attributes (e.g. `password` or `markdown`),
+    /// containers should forward them to their elements. The default impl ignores the options.
+    #[allow(unused_variables)]
+    fn inspect_with(&self, label: &str, ui: &mut egui::Ui, options: &InspectOptions) {
         self.inspect(label, ui);
     }
+    /// Returns the union of the responses of all widgets drawn for this value, in particular
+    /// `.changed()` is true if any part of the value was edited this frame.
+    fn inspect_mut(&mut self, label: &str, ui: &mut egui::Ui) -> Response {
+        ui.scope(|ui| self.inspect(label, ui)).response
+    }
+    /// [Self::inspect_mut] with the options set by field attributes, containers should forward
+    /// them to their elements. The default impl ignores the options.
+    #[allow(unused_variables)]
+    fn inspect_mut_with(
+        &mut self,
+        label: &str,
+        ui: &mut egui::Ui,
+        options: &InspectOptions,
+    ) -> Response {
+        self.inspect_mut(label, ui)
+    }
+    /// Draws only the contents of the value, without the frame, label or collapsing header it is
+    /// otherwise drawn in, as for fields inlined into their parent by `#[inspect(flatten)]`. The
+    /// default impl draws the value with an empty label.
+    fn inspect_flat(&self, ui: &mut egui::Ui) {
+        self.inspect("", ui)
+    }
+    /// Mutable [Self::inspect_flat]
+    fn inspect_flat_mut(&mut self, ui: &mut egui::Ui) -> Response {
+        self.inspect_mut("", ui)
+    }
+    /// Whether the value fits on a single line next to its label, used by grid layouts (see [grid])
+    /// in which nested structs and collections span the whole width instead
+    fn is_inline(&self) -> bool {
+        true
+    }
+    /// Whether the label of any field nested in this value matches the `query` of a field filter
+    /// (see [filter]), containers should forward it to their elements
+    #[allow(unused_variables)]
+    fn matches_filter(&self, query: &str) -> bool {
+        false
+    }
+    /// Errors reported by the `validate` functions of this value and of everything nested in it
+    /// (see [validation])
+    fn validation_errors(&self) -> Vec<ValidationError> {
+        vec![]
+    }
+    fn is_valid(&self) -> bool {
+        self.validation_errors().is_empty()
+    }
+    /// Draws `self` and `other` side by side, highlighting what differs (see [diff]), returns
+    /// whether they differ. The default impl cannot compare the values, it only draws both.
+    fn inspect_diff(&self, other: &Self, label: &str, ui: &mut egui::Ui) -> bool {
+        diff::diff_columns(
+            ui,
+            diff::DiffKind::Same,
+            |ui| self.inspect(label, ui),
+            |ui| other.inspect(label, ui),
+        );
+        false
+    }
+}
+
+/// Return types accepted from `custom_func_mut` functions. Returning `()` still works, but the
+/// generated inspect then has no way of knowing whether the value was changed.
+pub trait InspectReturn {
+    fn into_response(self, scope_response: Response) -> Response;
+}
+
+impl InspectReturn for () {
+    fn into_response(self, scope_response: Response) -> Response {
+        scope_response
+    }
+}
+
+impl InspectReturn for Response {
+    fn into_response(self, scope_response: Response) -> Response {
+        scope_response | self
+    }
 }
 
 pub struct FrameStyle {
@@ -101,10 +312,26 @@ impl FrameStyle {
 
 pub mod background_task;
 pub mod base_type_inspect;
+pub mod code_editor;
+pub mod diff;
 pub mod egui_types;
+pub mod filter;
+pub mod grid;
+pub mod groups;
+pub mod help;
 #[cfg(feature = "logging")]
 pub mod logging;
+pub mod markdown;
+pub mod options;
+pub mod pattern;
 pub mod search_select;
 #[cfg(feature = "toml")]
 pub mod serialization_types;
+pub mod staged;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
+pub mod undo;
 pub mod utils;
+pub mod validation;
+pub mod vec_editor;
+pub mod widget_state;
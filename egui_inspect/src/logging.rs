@@ -87,7 +87,7 @@ impl EguiInspect for GuiLogData {
         });
     }
 
-    fn inspect_mut(&mut self, _label: &str, _ui: &mut egui::Ui) {
+    fn inspect_mut(&mut self, _label: &str, _ui: &mut egui::Ui) -> egui::Response {
         todo!()
     }
 }
@@ -222,8 +222,13 @@ impl EguiInspect for LogsView {
         GUI_LOG_DATA.with_borrow(|f| f.inspect(label, ui));
     }
 
-    fn inspect_mut(&mut self, label: &str, ui: &mut egui::Ui) {
-        GUI_LOG_DATA.with_borrow(|f| f.inspect(label, ui));
+    fn inspect_mut(&mut self, label: &str, ui: &mut egui::Ui) -> egui::Response {
+        ui.scope(|ui| GUI_LOG_DATA.with_borrow(|f| f.inspect(label, ui)))
+            .response
+    }
+
+    fn is_inline(&self) -> bool {
+        false
     }
 }
 
@@ -0,0 +1,480 @@
+//! Rendering of Markdown text, for `String` fields annotated with `#[inspect(markdown)]` and for
+//! the [Markdown] newtype, e.g. as the result of a [crate::background_task::BackgroundTask].
+//! Mutable inspects edit the text next to a live preview.
+//!
+//! The text is parsed as CommonMark by [pulldown_cmark], and drawn with:
+//! - headings and thematic breaks (`---`)
+//! - paragraphs, whose lines are joined
+//! - bullet and ordered lists, nested
+//! - code blocks, highlighted as their language with the `syntax_highlighting` feature (see
+//!   [crate::code_editor])
+//! - block quotes
+//! - inline `code`, *emphasis* and **strong emphasis**, and links
+//!
+//! Anything else (e.g. HTML, or images) is shown as its text.
+//!
+//! ```
+//! # use egui_inspect::*;
+//! use egui_inspect::markdown::Markdown;
+//!
+//! #[derive(EguiInspect)]
+//! struct Report {
+//!     #[inspect(markdown)]
+//!     description: String,
+//!     summary: Markdown,
+//! }
+//! ```
+
+use egui::{Response, RichText, Ui};
+use pulldown_cmark::{CodeBlockKind, Event, Parser, Tag, TagEnd};
+
+use crate::code_editor::highlight;
+use crate::diff::diff_leaf;
+use crate::help::field_label;
+
+/// Markdown text (see [self]), rendered when inspected
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Markdown(pub String);
+
+impl From<String> for Markdown {
+    fn from(text: String) -> Self {
+        Self(text)
+    }
+}
+
+impl From<&str> for Markdown {
+    fn from(text: &str) -> Self {
+        Self(text.to_string())
+    }
+}
+
+impl crate::EguiInspect for Markdown {
+    fn inspect(&self, label: &str, ui: &mut Ui) {
+        markdown_view(&self.0, label, ui);
+    }
+
+    fn inspect_mut(&mut self, label: &str, ui: &mut Ui) -> Response {
+        markdown_edit(&mut self.0, label, ui)
+    }
+
+    fn is_inline(&self) -> bool {
+        false
+    }
+
+    fn inspect_diff(&self, other: &Self, label: &str, ui: &mut Ui) -> bool {
+        diff_leaf(self, other, label, ui)
+    }
+}
+
+/// Renders the Markdown `text`, below its label
+pub fn markdown_view(text: &str, label: &str, ui: &mut Ui) {
+    ui.vertical(|ui| {
+        if !label.is_empty() {
+            field_label(ui, label, ":");
+        }
+        show_blocks(&parse_blocks(text), ui);
+    });
+}
+
+/// Multiline edit of the Markdown `text`, next to its rendering
+pub fn markdown_edit(text: &mut String, label: &str, ui: &mut Ui) -> Response {
+    ui.vertical(|ui| {
+        let label = (!label.is_empty()).then(|| field_label(ui, label, ":"));
+        let rows = text.lines().count().max(4);
+        ui.columns(2, |columns| {
+            let response = columns[0].add(
+                egui::TextEdit::multiline(text)
+                    .desired_width(f32::INFINITY)
+                    .desired_rows(rows),
+            );
+            let response = match label {
+                Some(label) => response.labelled_by(label.id),
+                None => response,
+            };
+            show_blocks(&parse_blocks(text), &mut columns[1]);
+            response
+        })
+    })
+    .inner
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Block {
+    Heading(usize, Vec<Inline>),
+    Paragraph(Vec<Inline>),
+    List {
+        /// Number of the first item of ordered lists
+        start: Option<u64>,
+        items: Vec<Vec<Block>>,
+    },
+    Code {
+        language: String,
+        code: String,
+    },
+    Quote(Vec<Block>),
+    Rule,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Inline {
+    Text {
+        text: String,
+        strong: bool,
+        emphasis: bool,
+    },
+    Code(String),
+    Link {
+        text: String,
+        url: String,
+    },
+}
+
+fn parse_blocks(text: &str) -> Vec<Block> {
+    blocks(&mut Parser::new(text))
+}
+
+/// Blocks up to the end of the enclosing element (or of the text)
+fn blocks<'a>(events: &mut impl Iterator<Item = Event<'a>>) -> Vec<Block> {
+    let mut blocks = vec![];
+    // inlines outside of paragraphs, as in the items of tight lists
+    let mut loose = Inlines::default();
+    while let Some(event) = events.next() {
+        let event = match loose.push(event) {
+            Ok(()) => continue,
+            Err(event) => event,
+        };
+        if !loose.inlines.is_empty() {
+            blocks.push(Block::Paragraph(std::mem::take(&mut loose).inlines));
+        }
+        match event {
+            Event::Start(Tag::Paragraph) => blocks.push(Block::Paragraph(inlines(events))),
+            Event::Start(Tag::Heading { level, .. }) => {
+                blocks.push(Block::Heading(level as usize, inlines(events)));
+            }
+            Event::Start(Tag::BlockQuote(_)) => blocks.push(Block::Quote(self::blocks(events))),
+            Event::Start(Tag::CodeBlock(kind)) => {
+                let language = match kind {
+                    CodeBlockKind::Fenced(info) => {
+                        info.split_whitespace().next().unwrap_or("").to_string()
+                    }
+                    CodeBlockKind::Indented => String::new(),
+                };
+                let mut code = String::new();
+                for event in events.by_ref() {
+                    match event {
+                        Event::Text(text) => code.push_str(&text),
+                        _ => break,
+                    }
+                }
+                // NOTE: the last line ends with a line break too
+                if code.ends_with('\n') {
+                    code.pop();
+                }
+                blocks.push(Block::Code { language, code });
+            }
+            Event::Start(Tag::List(start)) => {
+                let mut items = vec![];
+                while let Some(Event::Start(Tag::Item)) = events.next() {
+                    items.push(self::blocks(events));
+                }
+                blocks.push(Block::List { start, items });
+            }
+            Event::Rule => blocks.push(Block::Rule),
+            Event::End(_) => break,
+            // other blocks (e.g. HTML) are shown as their contents
+            Event::Start(_) => blocks.extend(self::blocks(events)),
+            _ => {}
+        }
+    }
+    if !loose.inlines.is_empty() {
+        blocks.push(Block::Paragraph(loose.inlines));
+    }
+    blocks
+}
+
+/// Inlines up to the end of the enclosing paragraph or heading
+fn inlines<'a>(events: &mut impl Iterator<Item = Event<'a>>) -> Vec<Inline> {
+    let mut inlines = Inlines::default();
+    for event in events {
+        if let Err(Event::End(_)) = inlines.push(event) {
+            break;
+        }
+    }
+    inlines.inlines
+}
+
+/// Inlines being gathered, with the style of the text at this point
+#[derive(Default)]
+struct Inlines {
+    inlines: Vec<Inline>,
+    strong: usize,
+    emphasis: usize,
+    /// URL and text of the link being gathered
+    link: Option<(String, String)>,
+}
+
+impl Inlines {
+    /// Adds an inline event, any other event is given back
+    fn push<'a>(&mut self, event: Event<'a>) -> Result<(), Event<'a>> {
+        match event {
+            Event::Text(text) | Event::Html(text) | Event::InlineHtml(text) => self.text(&text),
+            Event::Code(code) => match &mut self.link {
+                Some((_, text)) => text.push_str(&code),
+                None => self.inlines.push(Inline::Code(code.to_string())),
+            },
+            Event::SoftBreak => self.text(" "),
+            Event::HardBreak => self.text("\n"),
+            Event::Start(Tag::Strong) => self.strong += 1,
+            Event::End(TagEnd::Strong) => self.strong -= 1,
+            Event::Start(Tag::Emphasis) => self.emphasis += 1,
+            Event::End(TagEnd::Emphasis) => self.emphasis -= 1,
+            Event::Start(Tag::Link { dest_url, .. }) => {
+                self.link = Some((dest_url.to_string(), String::new()));
+            }
+            Event::End(TagEnd::Link) => {
+                if let Some((url, text)) = self.link.take() {
+                    self.inlines.push(Inline::Link { text, url });
+                }
+            }
+            // images are shown as their description
+            Event::Start(Tag::Image { .. }) | Event::End(TagEnd::Image) => {}
+            event => return Err(event),
+        }
+        Ok(())
+    }
+
+    fn text(&mut self, text: &str) {
+        if let Some((_, link_text)) = &mut self.link {
+            link_text.push_str(text);
+            return;
+        }
+        let (strong, emphasis) = (self.strong > 0, self.emphasis > 0);
+        match self.inlines.last_mut() {
+            Some(Inline::Text {
+                text: last,
+                strong: last_strong,
+                emphasis: last_emphasis,
+            }) if (*last_strong, *last_emphasis) == (strong, emphasis) => last.push_str(text),
+            _ => self.inlines.push(Inline::Text {
+                text: text.to_string(),
+                strong,
+                emphasis,
+            }),
+        }
+    }
+}
+
+fn show_blocks(blocks: &[Block], ui: &mut Ui) {
+    for block in blocks {
+        match block {
+            Block::Heading(level, title) => {
+                let title: String = title.iter().map(plain_text).collect();
+                let title = match level {
+                    1 => RichText::new(title).heading(),
+                    2 => RichText::new(title)
+                        .heading()
+                        .size(ui.text_style_height(&egui::TextStyle::Heading) * 0.8),
+                    _ => RichText::new(title).strong(),
+                };
+                ui.label(title);
+            }
+            Block::Paragraph(inlines) => show_inlines(inlines, ui),
+            Block::List { start, items } => {
+                for (i, item) in items.iter().enumerate() {
+                    ui.horizontal_top(|ui| {
+                        match start {
+                            Some(start) => ui.label(format!("{}.", start + i as u64)),
+                            None => ui.label("•"),
+                        };
+                        ui.vertical(|ui| show_blocks(item, ui));
+                    });
+                }
+            }
+            Block::Code { language, code } => {
+                egui::Frame::canvas(ui.style()).show(ui, |ui| {
+                    let job = highlight(ui, code, language);
+                    ui.add(egui::Label::new(job).selectable(true).extend());
+                });
+            }
+            Block::Quote(blocks) => {
+                let margin = egui::Margin {
+                    left: 12,
+                    ..Default::default()
+                };
+                let rect = egui::Frame::new()
+                    .inner_margin(margin)
+                    .show(ui, |ui| show_blocks(blocks, ui))
+                    .response
+                    .rect;
+                let stroke = ui.visuals().widgets.noninteractive.bg_stroke;
+                ui.painter()
+                    .vline(rect.left() + 4.0, rect.y_range(), stroke);
+            }
+            Block::Rule => {
+                ui.separator();
+            }
+        }
+    }
+}
+
+fn show_inlines(inlines: &[Inline], ui: &mut Ui) {
+    ui.horizontal_wrapped(|ui| {
+        ui.spacing_mut().item_spacing.x = 0.0;
+        for inline in inlines {
+            match inline {
+                Inline::Text {
+                    text,
+                    strong,
+                    emphasis,
+                } => {
+                    let mut text = RichText::new(text);
+                    if *strong {
+                        text = text.strong();
+                    }
+                    if *emphasis {
+                        text = text.italics();
+                    }
+                    ui.label(text);
+                }
+                Inline::Code(code) => {
+                    ui.label(RichText::new(code).code());
+                }
+                Inline::Link { text, url } => {
+                    ui.hyperlink_to(text, url);
+                }
+            }
+        }
+    });
+}
+
+fn plain_text(inline: &Inline) -> &str {
+    match inline {
+        Inline::Text { text, .. } | Inline::Code(text) | Inline::Link { text, .. } => text,
+    }
+}
+
+#[test]
+fn markdown_is_parsed_into_blocks() {
+    let text = "# Report *v2*\n\nRan `cargo test` on **all**\ncrates, see [the logs](https://example.com/logs).\n\n\
+        1. parsing\n2. layout\n   - nested_item\n\n   second paragraph\n- other list\n\n\
+        > quoted\n> text\n\n```rust\nfn main() {}\n```\n---\n<https://example.com>";
+    let plain = |text: &str| Inline::Text {
+        text: text.to_string(),
+        strong: false,
+        emphasis: false,
+    };
+    let paragraph = |text: &str| Block::Paragraph(vec![plain(text)]);
+    let expected = vec![
+        Block::Heading(
+            1,
+            vec![
+                plain("Report "),
+                Inline::Text {
+                    text: "v2".to_string(),
+                    strong: false,
+                    emphasis: true,
+                },
+            ],
+        ),
+        Block::Paragraph(vec![
+            plain("Ran "),
+            Inline::Code("cargo test".to_string()),
+            plain(" on "),
+            Inline::Text {
+                text: "all".to_string(),
+                strong: true,
+                emphasis: false,
+            },
+            plain(" crates, see "),
+            Inline::Link {
+                text: "the logs".to_string(),
+                url: "https://example.com/logs".to_string(),
+            },
+            plain("."),
+        ]),
+        Block::List {
+            start: Some(1),
+            items: vec![
+                vec![paragraph("parsing")],
+                vec![
+                    paragraph("layout"),
+                    Block::List {
+                        start: None,
+                        items: vec![vec![paragraph("nested_item")]],
+                    },
+                    paragraph("second paragraph"),
+                ],
+            ],
+        },
+        Block::List {
+            start: None,
+            items: vec![vec![paragraph("other list")]],
+        },
+        Block::Quote(vec![paragraph("quoted text")]),
+        Block::Code {
+            language: "rust".to_string(),
+            code: "fn main() {}".to_string(),
+        },
+        Block::Rule,
+        Block::Paragraph(vec![Inline::Link {
+            text: "https://example.com".to_string(),
+            url: "https://example.com".to_string(),
+        }]),
+    ];
+    assert_eq!(parse_blocks(text), expected);
+
+    // unmatched delimiters and escapes stay as they are
+    assert_eq!(
+        parse_blocks(r"2 * 3 = 6, \*not emphasis\*, snake_case_name and `a"),
+        vec![paragraph(
+            "2 * 3 = 6, *not emphasis*, snake_case_name and `a"
+        )]
+    );
+}
+
+#[test]
+fn markdown_indented_by_any_whitespace_is_parsed() {
+    let item = |text: &str| {
+        vec![Block::Paragraph(vec![Inline::Text {
+            text: text.to_string(),
+            strong: false,
+            emphasis: false,
+        }])]
+    };
+    // only spaces and tabs indent, other whitespace is text
+    assert_eq!(parse_blocks("\u{2003}- item"), item("\u{2003}- item"));
+    assert_eq!(
+        parse_blocks("- a\n\u{3000}- b"),
+        vec![Block::List {
+            start: None,
+            items: vec![item("a \u{3000}- b")],
+        }]
+    );
+    let nested = Block::List {
+        start: None,
+        items: vec![[
+            item("a"),
+            vec![Block::List {
+                start: None,
+                items: vec![item("b")],
+            }],
+        ]
+        .concat()],
+    };
+    assert_eq!(parse_blocks("- a\n\t- b"), vec![nested]);
+
+    let mut harness =
+        crate::testing::Harness::new(Markdown::from("\u{2003}- item")).with_label("notes");
+    assert!(harness.type_text("notes", "\u{3000}- a\n\t- b"));
+}
+
+#[test]
+fn markdown_is_rendered_and_previewed() {
+    let mut harness = crate::testing::Harness::new(Markdown::from("see [docs](https://docs.rs)"))
+        .with_label("notes");
+    assert!(harness.widget_tree().contains(r#"Label "docs""#));
+    assert!(harness.type_text("notes", "# Title"));
+    // the harness presses enter after typing
+    assert_eq!(harness.value, Markdown::from("# Title\n"));
+    assert!(harness.widget_tree().contains(r#"Label = "Title""#));
+}
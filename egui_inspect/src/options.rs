@@ -0,0 +1,195 @@
+use std::ops::RangeInclusive;
+
+/// Options set through field attributes (`slider`, `min`, `max`, `multiline`, ...), passed to
+/// [crate::EguiInspect::inspect_mut_with] at runtime. Primitives read the options that apply to
+/// them, and containers forward them to their elements, so that e.g.
+/// ```
+/// # use egui_inspect::*;
+/// #[derive(EguiInspect)]
+/// struct Weights {
+///     #[inspect(slider, min = 0, max = 1)]
+///     weights: Vec<f32>,
+/// }
+/// ```
+/// draws a slider for each weight. Options that do not apply to a type are ignored.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct InspectOptions {
+    /// Use a slider for numbers (ranging over `min..=max`, defaulting to `0..=100`)
+    pub slider: bool,
+    /// Use a logarithmic slider for numbers
+    pub log_slider: bool,
+    /// Min value for numbers
+    pub min: Option<NumberBound>,
+    /// Max value for numbers
+    pub max: Option<NumberBound>,
+    /// Numbers snap to multiples of this step (from `min`, or `0`)
+    pub step: Option<f64>,
+    /// How fast numbers change while dragged, per point
+    pub speed: Option<f64>,
+    /// Text shown before numbers (e.g. `"x: "`)
+    pub prefix: Option<String>,
+    /// Text shown after numbers, e.g. their unit (`" m/s"`)
+    pub suffix: Option<String>,
+    /// Number of decimals shown for numbers
+    pub decimals: Option<usize>,
+    /// How numbers are kept within `min..=max`
+    pub clamping: egui::SliderClamping,
+    /// How numbers are displayed (and parsed back once typed in)
+    pub format: NumberFormat,
+    /// Edit strings on multiple lines
+    pub multiline: bool,
+    /// Mask the text of strings, e.g. for passwords or API tokens
+    pub password: bool,
+    /// Placeholder shown while a string is empty
+    pub hint: Option<String>,
+    /// Max number of characters of strings
+    pub max_len: Option<usize>,
+    /// Characters allowed in strings, others are dropped as they are typed or pasted in
+    pub charset: Option<Charset>,
+    /// Pattern strings should match (see [crate::pattern]), flagged while they do not
+    pub regex: Option<String>,
+    /// Edit strings as source code in this language (see [crate::code_editor])
+    pub code: Option<String>,
+    /// Edit strings as Markdown, next to their rendering (see [crate::markdown])
+    pub markdown: bool,
+}
+
+/// Bound of a number as written in the attribute, so that integer bounds stay exact (e.g. near
+/// `u64::MAX`, which `f64` cannot represent)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum NumberBound {
+    Int(i128),
+    Float(f64),
+}
+
+impl From<f64> for NumberBound {
+    fn from(value: f64) -> Self {
+        Self::Float(value)
+    }
+}
+
+impl From<i128> for NumberBound {
+    fn from(value: i128) -> Self {
+        Self::Int(value)
+    }
+}
+
+/// Display format of numbers, attribute `format = "..."`
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum NumberFormat {
+    #[default]
+    Plain,
+    /// Hexadecimal, integers only
+    Hex,
+    /// Binary, integers only
+    Binary,
+    /// Scientific notation, e.g. `1.5e-3`
+    Scientific,
+    /// Fraction shown as a percentage, e.g. `0.25` as `25%`
+    Percent,
+    /// Thousands separated by commas, e.g. `1,234,567`
+    Thousands,
+}
+
+impl NumberFormat {
+    /// Formatter and parser of the format (`None` for [NumberFormat::Plain], and for the integer
+    /// formats drawn by egui itself)
+    #[allow(clippy::type_complexity)]
+    pub(crate) fn text_conversions(
+        self,
+        decimals: Option<usize>,
+    ) -> Option<(
+        Box<dyn Fn(f64, RangeInclusive<usize>) -> String>,
+        Box<dyn Fn(&str) -> Option<f64>>,
+    )> {
+        let plain = move |n: f64, range: RangeInclusive<usize>| match decimals {
+            Some(decimals) => format!("{n:.decimals$}"),
+            None => egui::emath::format_with_decimals_in_range(n, range),
+        };
+        match self {
+            NumberFormat::Plain | NumberFormat::Hex | NumberFormat::Binary => None,
+            NumberFormat::Scientific => Some((
+                Box::new(move |n, _| match decimals {
+                    Some(decimals) => format!("{n:.decimals$e}"),
+                    None => format!("{n:e}"),
+                }),
+                Box::new(|text| text.trim().parse().ok()),
+            )),
+            NumberFormat::Percent => Some((
+                Box::new(move |n, range| format!("{}%", plain(n * 100.0, range))),
+                Box::new(|text| {
+                    let text = text.trim().trim_end_matches('%').trim_end();
+                    text.parse::<f64>().ok().map(|percent| percent / 100.0)
+                }),
+            )),
+            NumberFormat::Thousands => Some((
+                Box::new(move |n, range| with_thousands_separators(&plain(n, range))),
+                Box::new(|text| text.trim().replace(',', "").parse().ok()),
+            )),
+        }
+    }
+}
+
+/// Characters allowed in a string, attribute `charset = "..."`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Charset {
+    Ascii,
+    Alphanumeric,
+    Digits,
+    /// Hexadecimal digits, either case
+    Hex,
+    /// Alphanumeric and `_`
+    Identifier,
+    /// Anything but path separators, control characters and the characters reserved on Windows
+    /// (`<>:"/\|?*`)
+    FileName,
+}
+
+impl Charset {
+    pub fn allows(self, c: char) -> bool {
+        match self {
+            Charset::Ascii => c.is_ascii(),
+            Charset::Alphanumeric => c.is_alphanumeric(),
+            Charset::Digits => c.is_ascii_digit(),
+            Charset::Hex => c.is_ascii_hexdigit(),
+            Charset::Identifier => c.is_alphanumeric() || c == '_',
+            Charset::FileName => !c.is_control() && !r#"<>:"/\|?*"#.contains(c),
+        }
+    }
+}
+
+/// `1234567.5` as `1,234,567.5`
+fn with_thousands_separators(number: &str) -> String {
+    let (sign, digits) = match number.strip_prefix('-') {
+        Some(digits) => ("-", digits),
+        None => ("", number),
+    };
+    let (int, fraction) = match digits.split_once('.') {
+        Some((int, fraction)) => (int, format!(".{fraction}")),
+        None => (digits, String::new()),
+    };
+    let mut grouped = String::new();
+    for (i, digit) in int.chars().enumerate() {
+        if i > 0 && (int.len() - i) % 3 == 0 {
+            grouped.push(',');
+        }
+        grouped.push(digit);
+    }
+    format!("{sign}{grouped}{fraction}")
+}
+
+#[test]
+fn formats_round_trip() {
+    let (format, parse) = NumberFormat::Thousands.text_conversions(None).unwrap();
+    assert_eq!(format(-1234567.0, 0..=2), "-1,234,567");
+    assert_eq!(format(1234.5, 0..=2), "1,234.5");
+    assert_eq!(parse("-1,234,567.5"), Some(-1234567.5));
+
+    let (format, parse) = NumberFormat::Percent.text_conversions(Some(1)).unwrap();
+    assert_eq!(format(0.255, 0..=6), "25.5%");
+    assert_eq!(parse("12 %"), Some(0.12));
+
+    let (format, parse) = NumberFormat::Scientific.text_conversions(Some(2)).unwrap();
+    assert_eq!(format(0.00125, 0..=6), "1.25e-3");
+    assert_eq!(parse("4e3"), Some(4000.0));
+}
@@ -0,0 +1,97 @@
+//! Matching of strings against the `regex` attribute of `String` fields, in the syntax of the
+//! [regex] crate, which matches in linear time whatever the pattern.
+//!
+//! The whole string has to match (`^` and `$` are implied, and accepted).
+//!
+//! ```
+//! # use egui_inspect::pattern::Pattern;
+//! let file_name = Pattern::new(r"[\w-]+(\.[a-z]{1,4})?").unwrap();
+//! assert!(file_name.is_match("report-2024.pdf"));
+//! assert!(!file_name.is_match("../secrets"));
+//! ```
+//!
+//! The patterns of `regex` attributes are checked when deriving, and those given at runtime (as
+//! [crate::InspectOptions::regex]) are compiled once, see [cached].
+
+use egui::{Id, Ui};
+use regex::Regex;
+
+use crate::widget_state::{load_state, store_state};
+
+/// A compiled pattern, see [self]
+#[derive(Clone, Debug)]
+pub struct Pattern {
+    regex: Regex,
+}
+
+impl Pattern {
+    /// Compiles `pattern`, the error describes what is malformed
+    pub fn new(pattern: &str) -> Result<Self, String> {
+        let error = |e| match e {
+            regex::Error::Syntax(e) => e,
+            e => e.to_string(),
+        };
+        // NOTE: checked alone first, as e.g. `a)|(b` is only valid once anchored
+        Regex::new(pattern).map_err(error)?;
+        let regex = Regex::new(&format!("^(?:{pattern})$")).map_err(error)?;
+        Ok(Self { regex })
+    }
+
+    /// Whether the whole of `text` matches
+    pub fn is_match(&self, text: &str) -> bool {
+        self.regex.is_match(text)
+    }
+}
+
+/// [Pattern::new] of `pattern`, compiled once and kept in [egui::Memory] for the following frames
+pub fn cached(ui: &Ui, pattern: &str) -> Result<Pattern, String> {
+    let id = Id::new(("egui_inspect::pattern", pattern));
+    let cached: Option<Result<Pattern, String>> = load_state(ui, id);
+    cached.unwrap_or_else(|| {
+        let compiled = Pattern::new(pattern);
+        store_state(ui, id, Some(compiled.clone()));
+        compiled
+    })
+}
+
+#[test]
+fn patterns_match_whole_strings() {
+    let hex_id = Pattern::new("^[0-9a-fA-F]{8}(-[0-9a-f]{4}){0,2}$").unwrap();
+    assert!(hex_id.is_match("DEADbeef"));
+    assert!(hex_id.is_match("deadbeef-0123-4567"));
+    assert!(!hex_id.is_match("deadbeef-0123-4567-89ab"));
+    assert!(!hex_id.is_match("deadbee"));
+
+    let token = Pattern::new(r"(sk|pk)_\w+").unwrap();
+    assert!(token.is_match("sk_live_123"));
+    assert!(!token.is_match("xk_live"));
+    assert!(!token.is_match("sk_"));
+
+    assert!(Pattern::new(r"a.*b\.c").unwrap().is_match("axxb.c"));
+    assert!(Pattern::new("(a|ab)(c|bcd)").unwrap().is_match("abcd"));
+    assert!(Pattern::new("[^/]*").unwrap().is_match(""));
+    assert!(Pattern::new("(a*)*").unwrap().is_match("aaa"));
+    assert!(Pattern::new("(a*){2}").unwrap().is_match(""));
+    // alternatives are all anchored
+    assert!(!Pattern::new("a|b").unwrap().is_match("ab"));
+
+    assert!(Pattern::new("a{2,1}").is_err());
+    assert!(Pattern::new("(ab").is_err());
+    assert!(Pattern::new("*a").is_err());
+    assert!(Pattern::new("[a-").is_err());
+    assert!(Pattern::new(r"a\qb").is_err());
+    assert!(Pattern::new("a)|(b").is_err());
+}
+
+#[test]
+fn escapes_and_nested_repetitions() {
+    assert!(Pattern::new(r"a\tb").unwrap().is_match("a\tb"));
+    assert!(!Pattern::new(r"a\tb").unwrap().is_match("atb"));
+    assert!(Pattern::new(r"\bword\b").unwrap().is_match("word"));
+
+    // matched without backtracking
+    let nested = Pattern::new("(a+)+b").unwrap();
+    let started = std::time::Instant::now();
+    assert!(!nested.is_match(&"a".repeat(64)));
+    assert!(started.elapsed() < std::time::Duration::from_secs(1));
+}
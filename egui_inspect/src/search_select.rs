@@ -1,10 +1,14 @@
 use std::ops::Range;
 
 use crate as egui_inspect;
-use crate::{utils::concat_rich_text, EguiInspect};
+use crate::{
+    utils::{concat_rich_text, empty_response},
+    widget_state::with_state,
+    EguiInspect,
+};
 use derive_getters::Getters;
 use egui::text::LayoutJob;
-use egui::{self, Color32, RichText, ScrollArea};
+use egui::{self, Color32, Response, RichText, ScrollArea};
 use fuzzy_matcher::skim::SkimMatcherV2;
 use fuzzy_matcher::FuzzyMatcher;
 
@@ -50,39 +54,73 @@ impl SearchMethod for FuzzySearch {
     }
 }
 
+#[derive(Clone)]
 pub struct Match {
     idx: usize,
     char_idxs: Vec<usize>,
     score: i64,
 }
 
-/// Wrapper around Vec<String> which also holds persistent [search_ui] related data
+/// Transient [TextSearch] ui state, kept in [egui::Memory] (see [crate::widget_state])
+#[derive(Clone, Default)]
+struct TextSearchState {
+    input: String,
+    /// hash of the options the cache was computed for
+    options_hash: u64,
+    cache: Vec<Match>,
+}
+
+/// Wrapper around Vec<String> which also holds the selection made through its [search_ui],
+/// `inspect_mut(..).changed()` signals a new selection
 #[derive(Getters)]
 pub struct TextSearch {
     options: Vec<String>,
-    input: String,
     selected: Option<usize>,
-    just_clicked: bool,
     #[getter(skip)]
     pub display_selected: bool,
     #[getter(skip)]
     pub fuzzy: bool,
-    #[getter(skip)]
-    cache: Vec<Match>,
 }
 
 impl TextSearch {
     pub fn new(options: impl Into<Vec<String>>, fuzzy: bool) -> Self {
         TextSearch {
             options: options.into(),
-            input: String::new(),
             selected: None,
-            just_clicked: false,
             display_selected: true,
             fuzzy,
-            cache: vec![],
         }
     }
+
+    fn update_cache(&self, state: &mut TextSearchState) {
+        if state.input.is_empty() {
+            state.cache = (0..self.options.len())
+                .map(|idx| Match {
+                    idx,
+                    char_idxs: vec![],
+                    score: 0,
+                })
+                .collect();
+            return;
+        }
+        let match_idxs = match self.fuzzy {
+            true => FuzzySearch::match_idxs,
+            false => BasicSearch::match_idxs,
+        };
+        state.cache = self
+            .options
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, entry)| {
+                match_idxs(entry, &state.input).map(|(score, char_idxs)| Match {
+                    idx,
+                    char_idxs,
+                    score,
+                })
+            })
+            .collect();
+        state.cache.sort_by_key(|t| t.score);
+    }
 }
 
 impl EguiInspect for TextSearch {
@@ -93,63 +131,55 @@ impl EguiInspect for TextSearch {
         };
     }
 
-    fn inspect_mut(&mut self, label: &str, ui: &mut egui::Ui) {
-        self.just_clicked = false;
+    fn inspect_mut(&mut self, label: &str, ui: &mut egui::Ui) -> Response {
         if self.options.is_empty() {
-            ui.label("<no available options>");
-        } else {
-            let match_idxs = match self.fuzzy {
-                true => FuzzySearch::match_idxs,
-                false => BasicSearch::match_idxs,
-            };
-            ui.horizontal(|ui| {
-                ui.label("search for:");
-                if ui.text_edit_singleline(&mut self.input).changed() {
-                    self.cache = self
-                        .options
-                        .iter()
-                        .enumerate()
-                        .filter_map(|(idx, entry)| {
-                            match_idxs(entry, &self.input).map(|(score, char_idxs)| Match {
-                                idx,
-                                char_idxs,
-                                score,
-                            })
-                        })
-                        .collect();
-                    self.cache.sort_by_key(|t| t.score);
-                }
-            });
-            if self.input.is_empty() && self.cache.len() != self.options.len() {
-                self.cache = (0..self.options.len())
-                    .map(|idx| Match {
-                        idx,
-                        char_idxs: vec![],
-                        score: 0,
-                    })
-                    .collect();
+            return ui.label("<no available options>");
+        }
+        let options_hash = egui::Id::new((&self.options, self.fuzzy)).value();
+        let state_id = ui.id().with("text_search");
+        let response = with_state(ui, state_id, |state: &mut TextSearchState, ui| {
+            let mut response = ui
+                .horizontal(|ui| {
+                    ui.label("search for:");
+                    ui.text_edit_singleline(&mut state.input)
+                })
+                .inner;
+            if response.changed() || state.options_hash != options_hash {
+                self.update_cache(state);
+                state.options_hash = options_hash;
             }
-            ScrollArea::vertical().max_height(100.0).show(ui, |ui| {
-                for Match { idx, char_idxs, .. } in self.cache.iter().rev() {
-                    let entry = &self.options[*idx];
-                    if ui
-                        .button(non_contiguous_highlight(
-                            entry,
-                            char_idxs,
-                            Color32::GREEN,
-                            Color32::WHITE,
-                        ))
-                        .clicked()
-                    {
-                        self.selected = Some(*idx);
-                        self.just_clicked = true
+            response |= ScrollArea::vertical()
+                .max_height(100.0)
+                .show(ui, |ui| {
+                    let mut response = empty_response(ui);
+                    for Match { idx, char_idxs, .. } in state.cache.iter().rev() {
+                        let entry = &self.options[*idx];
+                        if ui
+                            .button(non_contiguous_highlight(
+                                entry,
+                                char_idxs,
+                                Color32::GREEN,
+                                Color32::WHITE,
+                            ))
+                            .clicked()
+                        {
+                            self.selected = Some(*idx);
+                            response.mark_changed();
+                        }
                     }
-                }
-            });
-            if self.display_selected {
-                self.inspect(label, ui)
-            }
+                    response
+                })
+                .inner;
+            response
+        });
+        if self.display_selected {
+            self.inspect(label, ui)
         }
+        response
+    }
+
+    fn is_inline(&self) -> bool {
+        false
     }
 }
 
@@ -185,11 +215,10 @@ impl<I> SearchSelection<I> {
         &self.items
     }
 
+    /// the search query is kept, as it lives in the ui state rather than in [TextSearch]
     pub fn mut_items_with(&mut self, items_mut_fn: impl Fn(&mut Vec<I>)) {
         items_mut_fn(&mut self.items);
-        let old_search = self.search.input.clone();
         self.reset_search_text();
-        self.search.input = old_search;
     }
 
     pub fn get_selected_ref(&self) -> Option<&I> {
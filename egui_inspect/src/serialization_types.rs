@@ -3,15 +3,20 @@
 // array or hashmap/object-field inserting/removing).
 
 use chrono::Datelike;
+use egui::Response;
 use egui_extras::DatePickerButton;
 use toml::value::Date;
 
+use crate::base_type_inspect::{diff_entries, diff_sequences};
+use crate::diff::diff_leaf;
+use crate::utils::{collapsing_response, empty_response};
+
 impl crate::EguiInspect for toml::value::Date {
     fn inspect(&self, label: &str, ui: &mut egui::Ui) {
         format!("{self}").inspect(label, ui)
     }
 
-    fn inspect_mut(&mut self, label: &str, ui: &mut egui::Ui) {
+    fn inspect_mut(&mut self, label: &str, ui: &mut egui::Ui) -> Response {
         ui.horizontal(|ui| {
             ui.label(label);
             let mut cdt = chrono::naive::NaiveDate::from_ymd_opt(
@@ -20,17 +25,23 @@ impl crate::EguiInspect for toml::value::Date {
                 self.day.into(),
             );
             if let Some(cdt) = &mut cdt {
-                ui.add(DatePickerButton::new(cdt));
+                let response = ui.add(DatePickerButton::new(cdt));
                 // TODO: Careful casting?
                 *self = Date {
                     year: cdt.year() as u16,
                     month: cdt.month() as u8,
                     day: cdt.day() as u8,
-                }
+                };
+                response
             } else {
-                ui.label("<date error>");
+                ui.label("<date error>")
             }
-        });
+        })
+        .inner
+    }
+
+    fn inspect_diff(&self, other: &Self, label: &str, ui: &mut egui::Ui) -> bool {
+        diff_leaf(self, other, label, ui)
     }
 }
 
@@ -45,46 +56,68 @@ impl crate::EguiInspect for toml::Value {
             toml::Value::Array(arr) => {
                 ui.collapsing(label, |ui| {
                     for (i, item) in arr.iter().enumerate() {
-                        item.inspect(format!("{label}[{i}]").as_str(), ui);
+                        ui.push_id(i, |ui| item.inspect(format!("{label}[{i}]").as_str(), ui));
                     }
                 });
             }
             toml::Value::Table(tab) => {
                 ui.collapsing(label.to_string(), |ui| {
                     for (key, item) in tab.iter() {
-                        item.inspect(key.as_str(), ui);
+                        ui.push_id(key, |ui| item.inspect(key.as_str(), ui));
                     }
                 });
             }
         }
     }
 
-    fn inspect_mut(&mut self, label: &str, ui: &mut egui::Ui) {
+    fn inspect_mut(&mut self, label: &str, ui: &mut egui::Ui) -> Response {
         match self {
             toml::Value::String(s) => s.inspect_mut(label, ui),
             toml::Value::Integer(i) => i.inspect_mut(label, ui),
             toml::Value::Float(f) => f.inspect_mut(label, ui),
             toml::Value::Boolean(b) => b.inspect_mut(label, ui),
-            toml::Value::Datetime(dt) => {
-                if let Some(d) = &mut dt.date {
-                    d.inspect_mut(label, ui);
-                }
+            toml::Value::Datetime(dt) => match &mut dt.date {
+                Some(d) => d.inspect_mut(label, ui),
                 // TODO: handle time component of datetime...
-            }
-            toml::Value::Array(arr) => {
-                ui.collapsing(label, |ui| {
-                    for (i, item) in arr.iter_mut().enumerate() {
-                        item.inspect_mut(format!("{label}[{i}]").as_str(), ui);
-                    }
-                });
-            }
+                None => empty_response(ui),
+            },
+            toml::Value::Array(arr) => collapsing_response(ui.collapsing(label, |ui| {
+                let mut response = empty_response(ui);
+                for (i, item) in arr.iter_mut().enumerate() {
+                    response |= ui
+                        .push_id(i, |ui| {
+                            item.inspect_mut(format!("{label}[{i}]").as_str(), ui)
+                        })
+                        .inner;
+                }
+                response
+            })),
             toml::Value::Table(tab) => {
-                ui.collapsing(label.to_string(), |ui| {
+                collapsing_response(ui.collapsing(label.to_string(), |ui| {
+                    let mut response = empty_response(ui);
                     for (key, item) in tab.iter_mut() {
-                        item.inspect_mut(key.as_str(), ui);
+                        response |= ui
+                            .push_id(key, |ui| item.inspect_mut(key.as_str(), ui))
+                            .inner;
                     }
-                });
+                    response
+                }))
+            }
+        }
+    }
+
+    fn is_inline(&self) -> bool {
+        !matches!(self, toml::Value::Array(_) | toml::Value::Table(_))
+    }
+
+    fn inspect_diff(&self, other: &Self, label: &str, ui: &mut egui::Ui) -> bool {
+        match (self, other) {
+            (toml::Value::Array(l), toml::Value::Array(r)) => diff_sequences(l, r, label, ui),
+            (toml::Value::Table(l), toml::Value::Table(r)) => {
+                let keys = l.keys().chain(r.keys()).collect();
+                diff_entries(keys, |k| l.get(k), |k| r.get(k), label, ui)
             }
+            _ => diff_leaf(self, other, label, ui),
         }
     }
 }
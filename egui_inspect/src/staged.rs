@@ -0,0 +1,271 @@
+use std::ops::Deref;
+
+use egui::{Button, Modal, Response, Window};
+
+use crate::{
+    validation::{show_validation_errors, ValidationError},
+    EguiInspect, InspectOptions,
+};
+
+/// Where a [Staged] value is edited
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum StagedEditor {
+    /// In place, followed by Apply/Revert buttons
+    #[default]
+    Inline,
+    /// In a modal dialog with OK/Cancel buttons, blocking the rest of the ui
+    Modal,
+    /// In a separate window with OK/Cancel buttons
+    Window,
+}
+
+/// Wraps a value so that edits are made on a working copy, which is only committed to the value
+/// once applied (or confirmed with OK, when edited in a modal or window, see [StagedEditor]).
+///
+/// `inspect_mut(..).changed()` is only true on the frame the working copy is committed. Applying
+/// is disabled while the working copy is invalid (see [EguiInspect::is_valid]).
+pub struct Staged<T: EguiInspect + Clone> {
+    value: T,
+    working: T,
+    dirty: bool,
+    editor: StagedEditor,
+    /// whether the modal or window editor is shown
+    editing: bool,
+}
+
+impl<T: EguiInspect + Clone> Staged<T> {
+    pub fn new(value: T) -> Self {
+        Self {
+            working: value.clone(),
+            value,
+            dirty: false,
+            editor: StagedEditor::Inline,
+            editing: false,
+        }
+    }
+
+    pub fn with_editor(mut self, editor: StagedEditor) -> Self {
+        self.editor = editor;
+        self
+    }
+
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+
+    /// Replaces the value, discarding any pending edits
+    pub fn set(&mut self, value: T) {
+        self.working = value.clone();
+        self.value = value;
+        self.dirty = false;
+    }
+
+    pub fn working(&self) -> &T {
+        &self.working
+    }
+
+    /// Edits made through this are pending until applied
+    pub fn working_mut(&mut self) -> &mut T {
+        self.dirty = true;
+        &mut self.working
+    }
+
+    /// True if the working copy was edited since the last apply/revert
+    pub fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    /// Commits the working copy, returns false if it was not valid
+    pub fn apply(&mut self) -> bool {
+        if !self.working.is_valid() {
+            return false;
+        }
+        self.value = self.working.clone();
+        self.dirty = false;
+        true
+    }
+
+    /// Discards the edits made to the working copy
+    pub fn revert(&mut self) {
+        self.working = self.value.clone();
+        self.dirty = false;
+    }
+
+    fn inline_inspect_mut(
+        &mut self,
+        label: &str,
+        ui: &mut egui::Ui,
+        options: &InspectOptions,
+    ) -> Response {
+        let mut response = self.working.inspect_mut_with(label, ui, options);
+        self.dirty |= response.changed();
+        // only committing counts as a change of the value
+        response.flags.remove(egui::response::Flags::CHANGED);
+
+        let errors = self.working.validation_errors();
+        ui.horizontal(|ui| {
+            let apply = ui.add_enabled(self.dirty && errors.is_empty(), Button::new("Apply"));
+            if apply.clicked() && self.apply() {
+                response.mark_changed();
+            }
+            if ui.add_enabled(self.dirty, Button::new("Revert")).clicked() {
+                self.revert();
+            }
+            if self.dirty {
+                ui.colored_label(ui.visuals().warn_fg_color, "● unapplied changes");
+            }
+        });
+        response
+    }
+
+    /// Draws the OK/Cancel row of the modal and window editors, returns (committed, closed)
+    fn confirm_buttons(&mut self, ui: &mut egui::Ui) -> (bool, bool) {
+        let errors = self.working.validation_errors();
+        show_validation_errors(ui, &errors);
+        ui.horizontal(|ui| {
+            let ok = ui.add_enabled(errors.is_empty(), Button::new("OK"));
+            let committed = ok.clicked() && self.apply();
+            let cancelled = ui.button("Cancel").clicked();
+            (committed, committed || cancelled)
+        })
+        .inner
+    }
+
+    fn popup_inspect_mut(
+        &mut self,
+        label: &str,
+        ui: &mut egui::Ui,
+        options: &InspectOptions,
+    ) -> Response {
+        let mut response = ui
+            .horizontal(|ui| {
+                let edit = ui.add_enabled(!self.editing, Button::new("Edit"));
+                if edit.clicked() {
+                    self.revert();
+                    self.editing = true;
+                }
+                self.value.inspect(label, ui);
+                edit
+            })
+            .inner;
+        if !self.editing {
+            return response;
+        }
+
+        let popup_id = ui.id().with("staged_editor");
+        let title = match label.is_empty() {
+            true => "Edit",
+            false => label,
+        };
+        // NOTE: the editor lives on another layer, so its responses are not merged in
+        let (committed, closed) = match self.editor {
+            StagedEditor::Modal => {
+                let modal = Modal::new(popup_id).show(ui.ctx(), |ui| {
+                    ui.heading(title);
+                    self.dirty |= self.working.inspect_mut_with("", ui, options).changed();
+                    self.confirm_buttons(ui)
+                });
+                let (committed, closed) = modal.inner;
+                (committed, closed || modal.should_close())
+            }
+            _ => {
+                let mut open = true;
+                let window = Window::new(title)
+                    .id(popup_id)
+                    .open(&mut open)
+                    .show(ui.ctx(), |ui| {
+                        self.dirty |= self.working.inspect_mut_with("", ui, options).changed();
+                        self.confirm_buttons(ui)
+                    });
+                let (committed, closed) = window.and_then(|w| w.inner).unwrap_or((false, false));
+                (committed, closed || !open)
+            }
+        };
+        if committed {
+            response.mark_changed();
+        }
+        if closed {
+            self.revert();
+            self.editing = false;
+        }
+        response
+    }
+}
+
+impl<T: EguiInspect + Clone> Deref for Staged<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.value
+    }
+}
+
+impl<T: EguiInspect + Clone + Default> Default for Staged<T> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl<T: EguiInspect + Clone> From<T> for Staged<T> {
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<T: EguiInspect + Clone> EguiInspect for Staged<T> {
+    fn inspect(&self, label: &str, ui: &mut egui::Ui) {
+        self.value.inspect(label, ui);
+    }
+
+    fn inspect_with(&self, label: &str, ui: &mut egui::Ui, options: &InspectOptions) {
+        self.value.inspect_with(label, ui, options);
+    }
+
+    fn inspect_mut(&mut self, label: &str, ui: &mut egui::Ui) -> Response {
+        self.inspect_mut_with(label, ui, &InspectOptions::default())
+    }
+
+    fn inspect_mut_with(
+        &mut self,
+        label: &str,
+        ui: &mut egui::Ui,
+        options: &InspectOptions,
+    ) -> Response {
+        match self.editor {
+            StagedEditor::Inline => self.inline_inspect_mut(label, ui, options),
+            StagedEditor::Modal | StagedEditor::Window => {
+                self.popup_inspect_mut(label, ui, options)
+            }
+        }
+    }
+
+    fn is_inline(&self) -> bool {
+        self.editor != StagedEditor::Inline && self.value.is_inline()
+    }
+
+    fn matches_filter(&self, query: &str) -> bool {
+        self.value.matches_filter(query)
+    }
+
+    fn validation_errors(&self) -> Vec<ValidationError> {
+        self.value.validation_errors()
+    }
+
+    fn inspect_diff(&self, other: &Self, label: &str, ui: &mut egui::Ui) -> bool {
+        self.value.inspect_diff(&other.value, label, ui)
+    }
+}
+
+#[test]
+fn edits_are_only_committed_on_apply() {
+    let mut staged = Staged::new(1u32);
+    *staged.working_mut() = 2;
+    assert!(staged.is_dirty());
+    assert_eq!(*staged, 1);
+    staged.revert();
+    assert_eq!(*staged.working(), 1);
+    *staged.working_mut() = 3;
+    assert!(staged.apply());
+    assert_eq!(*staged, 3);
+    assert!(!staged.is_dirty());
+}
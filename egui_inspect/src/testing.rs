@@ -0,0 +1,661 @@
+//! Headless harness for testing inspect UIs (requires the `testing` feature): an inspected value is
+//! drawn (with [EguiInspect::inspect_mut]) inside an offscreen [egui::Context], where widgets can be
+//! found by their label (through the accessibility tree), clicked, dragged or typed into.
+//!
+//! ```
+//! # use egui_inspect::*;
+//! # use egui_inspect::testing::Harness;
+//! #[derive(EguiInspect, Default)]
+//! struct Params {
+//!     #[inspect(slider, min = 0, max = 10)]
+//!     iters: u32,
+//!     verbose: bool,
+//! }
+//!
+//! let mut harness = Harness::new(Params::default());
+//! assert!(harness.click("verbose"));
+//! assert!(harness.value.verbose);
+//! ```
+//!
+//! [Harness::widget_tree] dumps the drawn widgets as text, for golden file comparisons.
+
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use egui::accesskit::{Action, NodeId, Role, Toggled, TreeUpdate};
+use egui::{
+    CentralPanel, Context, Event, Key, Modifiers, PointerButton, Pos2, RawInput, Rect, Vec2,
+};
+
+use crate::{EguiInspect, InspectOptions};
+
+/// A widget as found in the accessibility tree
+#[derive(Clone, Debug)]
+pub struct WidgetNode {
+    pub role: Role,
+    /// label of the widget, or the text of a label
+    pub label: Option<String>,
+    /// text value (e.g. of a text edit)
+    pub value: Option<String>,
+    pub numeric_value: Option<f64>,
+    /// checked state of checkboxes (and selected state of selectable labels)
+    pub toggled: Option<bool>,
+    pub disabled: bool,
+    /// whether it responds to clicks or takes focus
+    pub interactive: bool,
+    pub rect: Rect,
+    /// nesting depth in the tree
+    pub depth: usize,
+}
+
+impl WidgetNode {
+    fn text(&self) -> Option<&str> {
+        self.label.as_deref().or(match self.role {
+            Role::Label => self.value.as_deref(),
+            _ => None,
+        })
+    }
+}
+
+/// Runs an inspected value in an offscreen context (see [self])
+pub struct Harness<T: EguiInspect> {
+    pub value: T,
+    ctx: Context,
+    label: String,
+    options: Option<InspectOptions>,
+    size: Vec2,
+    time: f64,
+    nodes: Vec<WidgetNode>,
+}
+
+impl<T: EguiInspect> Harness<T> {
+    pub fn new(value: T) -> Self {
+        let ctx = Context::default();
+        ctx.enable_accesskit();
+        // so that e.g. collapsing headers open within a frame
+        ctx.style_mut(|style| style.animation_time = 0.0);
+        let mut harness = Self {
+            value,
+            ctx,
+            label: String::new(),
+            options: None,
+            size: Vec2::new(800.0, 2000.0),
+            time: 0.0,
+            nodes: vec![],
+        };
+        harness.run();
+        harness
+    }
+
+    /// Label the value is inspected with (defaults to `""`)
+    pub fn with_label(mut self, label: &str) -> Self {
+        self.label = label.to_string();
+        self.run();
+        self
+    }
+
+    /// Inspect the value with [EguiInspect::inspect_mut_with] these options
+    pub fn with_options(mut self, options: InspectOptions) -> Self {
+        self.options = Some(options);
+        self.run();
+        self
+    }
+
+    /// Size of the offscreen screen (defaults to 800x2000), nothing is scrolled
+    pub fn with_size(mut self, size: Vec2) -> Self {
+        self.size = size;
+        self.run();
+        self
+    }
+
+    /// The offscreen context, e.g. to change settings stored in it
+    pub fn ctx(&self) -> &Context {
+        &self.ctx
+    }
+
+    /// Runs a frame without input, returns whether the value was reported as changed
+    pub fn run(&mut self) -> bool {
+        self.step(vec![])
+    }
+
+    fn step(&mut self, events: Vec<Event>) -> bool {
+        let input = RawInput {
+            screen_rect: Some(Rect::from_min_size(Pos2::ZERO, self.size)),
+            time: Some(self.time),
+            events,
+            ..Default::default()
+        };
+        // NOTE: short enough for a press and release in consecutive frames to count as a click
+        self.time += 0.1;
+
+        let Self {
+            value,
+            ctx,
+            label,
+            options,
+            ..
+        } = self;
+        let mut changed = false;
+        let output = ctx.run(input, |ctx| {
+            CentralPanel::default().show(ctx, |ui| {
+                let response = match options {
+                    Some(options) => value.inspect_mut_with(label, ui, options),
+                    None => value.inspect_mut(label, ui),
+                };
+                changed |= response.changed();
+            });
+        });
+        if let Some(update) = output.platform_output.accesskit_update {
+            self.nodes = flatten_tree(update);
+        }
+        changed
+    }
+
+    /// Runs the frames for a sequence of inputs, returns whether the value was reported as changed
+    /// in any of them
+    pub fn input(&mut self, frames: Vec<Vec<Event>>) -> bool {
+        let mut changed = false;
+        for events in frames {
+            changed |= self.step(events);
+        }
+        // settle, e.g. for widgets appearing after the change
+        self.run() | changed
+    }
+
+    /// All widgets drawn in the last frame, in tree order
+    pub fn nodes(&self) -> &[WidgetNode] {
+        &self.nodes
+    }
+
+    /// The widget labelled `label`, otherwise the interactive widget next to a label with the text
+    /// `label` (or `label:`), as with fields drawn as a label followed by e.g. a `DragValue`
+    pub fn query(&self, label: &str) -> Option<&WidgetNode> {
+        if let Some(node) = self
+            .nodes
+            .iter()
+            .find(|node| node.interactive && node.label.as_deref() == Some(label))
+        {
+            return Some(node);
+        }
+        let text = self.nodes.iter().find(|node| {
+            node.role == Role::Label
+                && node
+                    .text()
+                    .is_some_and(|text| text.trim_end_matches(':') == label)
+        })?;
+        // nearest widget to the right, on the same row
+        self.nodes
+            .iter()
+            .filter(|node| {
+                node.interactive
+                    && node.rect.min.x >= text.rect.max.x
+                    && node.rect.y_range().contains(text.rect.center().y)
+            })
+            .min_by(|a, b| a.rect.min.x.total_cmp(&b.rect.min.x))
+    }
+
+    /// [Self::query] which panics (listing the widgets) if nothing is found
+    pub fn get(&self, label: &str) -> &WidgetNode {
+        self.query(label).unwrap_or_else(|| {
+            panic!(
+                "No widget found for {label:?}, the widgets are:\n{}",
+                self.widget_tree()
+            )
+        })
+    }
+
+    /// Clicks the widget found by [Self::get], returns whether the value was reported as changed
+    pub fn click(&mut self, label: &str) -> bool {
+        let pos = self.get(label).rect.center();
+        self.click_at(pos)
+    }
+
+    /// Clicks at `pos`, e.g. the center of a node found in [Self::nodes], returns whether the value
+    /// was reported as changed
+    pub fn click_at(&mut self, pos: Pos2) -> bool {
+        self.input(click_frames(pos))
+    }
+
+    /// Drags the widget found by [Self::get] by `delta`, returns whether the value was reported as
+    /// changed
+    pub fn drag(&mut self, label: &str, delta: Vec2) -> bool {
+        let pos = self.get(label).rect.center();
+        self.drag_at(pos, delta)
+    }
+
+    /// Drags from `pos` by `delta`, returns whether the value was reported as changed
+    pub fn drag_at(&mut self, pos: Pos2, delta: Vec2) -> bool {
+        self.input(vec![
+            vec![Event::PointerMoved(pos)],
+            vec![pointer_button(pos, true, Modifiers::NONE)],
+            vec![Event::PointerMoved(pos + delta * 0.5)],
+            vec![Event::PointerMoved(pos + delta)],
+            vec![pointer_button(pos + delta, false, Modifiers::NONE)],
+        ])
+    }
+
+    /// Focuses the widget found by [Self::get] by clicking it, selects all of its text and replaces
+    /// it by `text` (then presses enter), returns whether the value was reported as changed
+    pub fn type_text(&mut self, label: &str, text: &str) -> bool {
+        let pos = self.get(label).rect.center();
+        let mut frames = click_frames(pos);
+        frames.push(vec![
+            key_press(Key::A, Modifiers::COMMAND),
+            Event::Text(text.to_string()),
+        ]);
+        frames.push(vec![key_press(Key::Enter, Modifiers::NONE)]);
+        self.input(frames)
+    }
+
+    /// Presses a key (with the focused widget), returns whether the value was reported as changed
+    pub fn key_press(&mut self, key: Key, modifiers: Modifiers) -> bool {
+        self.input(vec![vec![key_press(key, modifiers)]])
+    }
+
+    /// Text dump of the widgets drawn in the last frame, one per line indented by depth, e.g.
+    /// `SpinButton = 3` or `CheckBox "verbose" [x]`
+    pub fn widget_tree(&self) -> String {
+        let mut tree = String::new();
+        // the root and panel nodes are skipped
+        for node in self.nodes.iter().filter(|node| node.depth > 1) {
+            let indent = "  ".repeat(node.depth - 2);
+            let _ = write!(tree, "{indent}{:?}", node.role);
+            if let Some(label) = &node.label {
+                let _ = write!(tree, " {label:?}");
+            }
+            if let Some(value) = &node.value {
+                let _ = write!(tree, " = {value:?}");
+            } else if let Some(value) = node.numeric_value {
+                let _ = write!(tree, " = {value}");
+            }
+            match node.toggled {
+                Some(true) => tree.push_str(" [x]"),
+                Some(false) => tree.push_str(" [ ]"),
+                None => {}
+            }
+            if node.disabled {
+                tree.push_str(" (disabled)");
+            }
+            tree.push('\n');
+        }
+        tree
+    }
+}
+
+fn pointer_button(pos: Pos2, pressed: bool, modifiers: Modifiers) -> Event {
+    Event::PointerButton {
+        pos,
+        button: PointerButton::Primary,
+        pressed,
+        modifiers,
+    }
+}
+
+fn click_frames(pos: Pos2) -> Vec<Vec<Event>> {
+    // NOTE: the pointer first hovers, as widgets are hit tested against the previous frame
+    vec![
+        vec![Event::PointerMoved(pos)],
+        vec![pointer_button(pos, true, Modifiers::NONE)],
+        vec![pointer_button(pos, false, Modifiers::NONE)],
+    ]
+}
+
+fn key_press(key: Key, modifiers: Modifiers) -> Event {
+    Event::Key {
+        key,
+        physical_key: None,
+        pressed: true,
+        repeat: false,
+        modifiers,
+    }
+}
+
+/// The nodes of a full tree update, in depth first order
+fn flatten_tree(update: TreeUpdate) -> Vec<WidgetNode> {
+    let nodes: HashMap<NodeId, _> = update.nodes.into_iter().collect();
+    let mut flat = vec![];
+    let mut stack = update
+        .tree
+        .map(|tree| vec![(tree.root, 0)])
+        .unwrap_or_default();
+    while let Some((id, depth)) = stack.pop() {
+        let Some(node) = nodes.get(&id) else {
+            continue;
+        };
+        let rect = node
+            .bounds()
+            .map(|r| {
+                Rect::from_min_max(
+                    Pos2::new(r.x0 as f32, r.y0 as f32),
+                    Pos2::new(r.x1 as f32, r.y1 as f32),
+                )
+            })
+            .unwrap_or(Rect::NOTHING);
+        // widgets drawn apart from their label (e.g. below it) are labelled by it
+        let labelled_by = node.labelled_by().iter().find_map(|id| {
+            let label = nodes.get(id)?;
+            let text = label.label().or(label.value())?;
+            Some(text.trim_end().trim_end_matches(':').to_string())
+        });
+        flat.push(WidgetNode {
+            role: node.role(),
+            label: node.label().map(str::to_string).or(labelled_by),
+            value: node.value().map(str::to_string),
+            numeric_value: node.numeric_value(),
+            toggled: node.toggled().map(|toggled| toggled == Toggled::True),
+            disabled: node.is_disabled(),
+            interactive: node.supports_action(Action::Click) || node.supports_action(Action::Focus),
+            rect,
+            depth,
+        });
+        stack.extend(
+            node.children()
+                .iter()
+                .rev()
+                .map(|child| (*child, depth + 1)),
+        );
+    }
+    flat
+}
+
+#[test]
+fn widget_tree_lists_widgets() {
+    let harness = Harness::new(3u8).with_label("n");
+    assert!(harness.widget_tree().contains("Label = \"n:\""));
+    assert_eq!(harness.get("n").role, Role::SpinButton);
+    assert_eq!(harness.get("n").numeric_value, Some(3.0));
+}
+
+#[test]
+fn derived_struct_fields_are_edited() {
+    use crate as egui_inspect;
+    use egui_inspect_derive::EguiInspect;
+
+    #[derive(EguiInspect, Default)]
+    struct Inner {
+        depth: u8,
+    }
+
+    #[derive(EguiInspect, Default)]
+    struct Point(f32, #[inspect(name = "y")] f32);
+
+    #[derive(EguiInspect, Default)]
+    struct Scene {
+        #[inspect(name = "Title")]
+        title: String,
+        #[inspect(hide)]
+        _hidden: u8,
+        #[inspect(no_edit)]
+        fixed: u8,
+        inner: Inner,
+        #[inspect(flatten)]
+        flat: Point,
+    }
+
+    let mut harness = Harness::new(Scene::default());
+    assert!(!harness.widget_tree().contains("_hidden"));
+    // not editable, so drawn as a label
+    assert_eq!(harness.get("fixed").role, Role::Label);
+
+    assert!(harness.type_text("Title", "scene"));
+    assert_eq!(harness.value.title, "scene");
+    assert!(harness.drag("depth", Vec2::new(20.0, 0.0)));
+    assert!(harness.value.inner.depth > 0);
+    // the fields of the flattened struct are drawn among those of the parent
+    assert!(harness.drag("y", Vec2::new(20.0, 0.0)));
+    assert!(harness.value.flat.1 > 0.0);
+}
+
+#[test]
+fn derived_tuple_structs_are_edited() {
+    use crate as egui_inspect;
+    use egui_inspect_derive::EguiInspect;
+
+    #[derive(EguiInspect, Default)]
+    struct Point(f32, #[inspect(name = "y")] f32);
+
+    #[derive(EguiInspect, Default)]
+    #[inspect(transparent)]
+    struct Meters(f32);
+
+    let mut harness = Harness::new(Point::default()).with_label("point");
+    assert!(harness.drag("Field 0", Vec2::new(20.0, 0.0)));
+    assert!(harness.value.0 > 0.0);
+    assert_eq!(harness.value.1, 0.0);
+
+    // drawn as its field, under the label of the struct
+    let mut harness = Harness::new(Meters(1.0)).with_label("length");
+    assert_eq!(harness.get("length").role, Role::SpinButton);
+    assert!(harness.drag("length", Vec2::new(20.0, 0.0)));
+    assert!(harness.value.0 > 1.0);
+}
+
+#[test]
+fn derived_tuple_fields_are_shown_and_enabled_conditionally() {
+    use crate as egui_inspect;
+    use egui_inspect_derive::EguiInspect;
+
+    #[derive(EguiInspect, Default)]
+    struct Gate(
+        #[inspect(name = "open")] bool,
+        #[inspect(name = "width", enabled_if = self.0)] u8,
+        #[inspect(name = "latch", visible_if = self.1 > 0)] bool,
+    );
+
+    let mut harness = Harness::new(Gate::default()).with_label("gate");
+    assert!(harness.get("width").disabled);
+    assert!(harness.query("latch").is_none());
+    harness.click("open");
+    assert!(!harness.get("width").disabled);
+    assert!(harness.drag("width", Vec2::new(20.0, 0.0)));
+    assert!(harness.click("latch"));
+    assert!(harness.value.2);
+}
+
+#[test]
+fn derived_enum_variants_are_selected() {
+    use crate as egui_inspect;
+    use egui_inspect_derive::EguiInspect;
+
+    #[derive(EguiInspect, Default, Debug, PartialEq)]
+    enum Shape {
+        #[default]
+        Empty,
+        Circle {
+            radius: u8,
+        },
+        #[inspect(name = "Box")]
+        Square(u8),
+    }
+
+    #[derive(EguiInspect, Default, Debug, PartialEq)]
+    #[inspect(radio)]
+    enum Mode {
+        #[default]
+        Off,
+        On,
+    }
+
+    #[derive(EguiInspect, Default)]
+    struct Scene {
+        shape: Shape,
+        mode: Mode,
+    }
+
+    let combo_box = |harness: &Harness<Scene>| {
+        let node = harness.nodes().iter().find(|node| node.role == Role::ComboBox);
+        node.expect("a combo box").rect.center()
+    };
+    let mut harness = Harness::new(Scene::default());
+    harness.click_at(combo_box(&harness));
+    assert!(harness.click("Box"));
+    assert_eq!(harness.value.shape, Shape::Square(0));
+
+    harness.click_at(combo_box(&harness));
+    assert!(harness.click("Circle"));
+    assert_eq!(harness.value.shape, Shape::Circle { radius: 0 });
+    assert!(harness.drag("radius", Vec2::new(20.0, 0.0)));
+    assert!(matches!(harness.value.shape, Shape::Circle { radius } if radius > 0));
+
+    assert!(harness.click("On"));
+    assert_eq!(harness.value.mode, Mode::On);
+}
+
+#[test]
+fn derived_groups_are_drawn_as_tabs() {
+    use crate as egui_inspect;
+    use egui_inspect_derive::EguiInspect;
+
+    #[derive(EguiInspect, Default)]
+    #[inspect(groups = "tabs")]
+    struct Settings {
+        name: String,
+        #[inspect(group = "Audio")]
+        muted: bool,
+        #[inspect(group = "Video", order = -1)]
+        fullscreen: bool,
+    }
+
+    let mut harness = Harness::new(Settings::default());
+    // ordered first, so its tab is open
+    assert!(harness.query("fullscreen").is_some());
+    assert!(harness.query("muted").is_none());
+    assert!(harness.query("name").is_some());
+
+    harness.click("Audio");
+    assert!(harness.query("fullscreen").is_none());
+    assert!(harness.click("muted"));
+    assert!(harness.value.muted);
+}
+
+#[test]
+fn derived_fields_are_shown_and_enabled_conditionally() {
+    use crate as egui_inspect;
+    use egui_inspect_derive::EguiInspect;
+
+    #[derive(EguiInspect, Default)]
+    struct Laser {
+        armed: bool,
+        #[inspect(enabled_if = *armed)]
+        power: u8,
+        #[inspect(visible_if = self.power > 0)]
+        cooldown: u8,
+    }
+
+    #[derive(EguiInspect)]
+    enum Light {
+        On {
+            dimmed: bool,
+            #[inspect(enabled_if = *dimmed)]
+            level: u8,
+            #[inspect(visible_if = self.is_bright())]
+            glare: bool,
+        },
+        Blinking {
+            #[inspect(group = "Timing")]
+            period: u8,
+            #[inspect(group = "Timing", visible_if = !self.is_bright())]
+            phase: u8,
+        },
+    }
+
+    impl Light {
+        fn is_bright(&self) -> bool {
+            matches!(self, Light::On { level, .. } if *level > 100)
+        }
+    }
+
+    let mut harness = Harness::new(Laser::default());
+    assert!(harness.get("power").disabled);
+    assert!(harness.query("cooldown").is_none());
+    harness.click("armed");
+    assert!(!harness.get("power").disabled);
+    assert!(harness.drag("power", Vec2::new(20.0, 0.0)));
+    assert!(harness.query("cooldown").is_some());
+
+    let light = Light::On {
+        dimmed: false,
+        level: 100,
+        glare: false,
+    };
+    let mut harness = Harness::new(light);
+    assert!(harness.get("level").disabled);
+    assert!(harness.query("glare").is_none());
+    harness.click("dimmed");
+    assert!(harness.drag("level", Vec2::new(20.0, 0.0)));
+    assert!(harness.click("glare"));
+    assert!(matches!(harness.value, Light::On { glare: true, .. }));
+}
+
+#[test]
+fn derived_enum_variants_are_switched_keeping_shared_fields() {
+    use crate as egui_inspect;
+    use egui_inspect_derive::EguiInspect;
+
+    // neither PartialEq nor Default
+    #[derive(EguiInspect, Debug)]
+    #[inspect(radio)]
+    enum Source {
+        File {
+            path: String,
+            retries: u8,
+        },
+        #[inspect(ctor = Source::Url { path: "https://".to_string(), retries: 3, timeout: 10 })]
+        Url {
+            path: String,
+            retries: u8,
+            timeout: u16,
+        },
+        Stdin {
+            path: std::string::String,
+        },
+    }
+
+    let file = Source::File {
+        path: "data.csv".to_string(),
+        retries: 1,
+    };
+    let mut harness = Harness::new(file);
+    // the fields shared with the file are carried over, the others are taken from the ctor
+    assert!(harness.click("Url"));
+    match &harness.value {
+        Source::Url {
+            path,
+            retries,
+            timeout,
+        } => assert_eq!((path.as_str(), *retries, *timeout), ("data.csv", 1, 10)),
+        other => panic!("{other:?}"),
+    }
+    // the path is written as another type, so it is not carried over
+    assert!(harness.click("Stdin"));
+    assert!(matches!(&harness.value, Source::Stdin { path } if path.is_empty()));
+    assert!(!harness.click("Stdin"));
+}
+
+#[test]
+fn derived_text_options_apply_to_read_only_container_elements() {
+    use crate as egui_inspect;
+    use egui_inspect_derive::EguiInspect;
+
+    #[derive(EguiInspect)]
+    struct Release {
+        #[inspect(no_edit, password)]
+        token: Option<String>,
+        #[inspect(no_edit, markdown)]
+        notes: Vec<String>,
+        #[inspect(no_edit, code = "rust")]
+        build: Option<String>,
+    }
+
+    let release = Release {
+        token: Some("hunter2".to_string()),
+        notes: vec!["**Fixed** crashes".to_string()],
+        build: Some("fn main() {\n}".to_string()),
+    };
+    let mut harness = Harness::new(release);
+    harness.click("notes");
+    let tree = harness.widget_tree();
+    assert!(!tree.contains("hunter2") && tree.contains("•••••••"), "{tree}");
+    assert!(!tree.contains("**Fixed**") && tree.contains("Fixed"), "{tree}");
+    assert!(tree.contains(r#""1\n2""#), "{tree}");
+}
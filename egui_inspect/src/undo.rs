@@ -0,0 +1,335 @@
+use std::collections::VecDeque;
+use std::ops::Deref;
+
+use egui::{Id, Key, KeyboardShortcut, Modifiers, Response};
+
+use crate::{validation::ValidationError, EguiInspect, InspectOptions};
+
+const UNDO_SHORTCUT: KeyboardShortcut = KeyboardShortcut::new(Modifiers::COMMAND, Key::Z);
+const REDO_SHORTCUTS: [KeyboardShortcut; 2] = [
+    KeyboardShortcut::new(Modifiers::COMMAND.plus(Modifiers::SHIFT), Key::Z),
+    KeyboardShortcut::new(Modifiers::COMMAND, Key::Y),
+];
+
+/// Wraps a value to record its edits (as reported by [EguiInspect::inspect_mut]) into a bounded
+/// undo history, offering undo/redo buttons and the Ctrl+Z / Ctrl+Shift+Z (or Ctrl+Y) shortcuts.
+///
+/// Consecutive changes made while the pointer is held down (dragging a `DragValue` or slider), or
+/// while the same widget keeps keyboard focus (typing), are merged into a single undo step.
+///
+/// The shortcuts apply to the `Undoable` that is hovered, or that holds the focused widget, and
+/// are ignored while a text field has focus, leaving it to its own undo.
+pub struct Undoable<T: EguiInspect + Clone> {
+    value: T,
+    /// copy of `value` as of the last recorded change
+    snapshot: T,
+    undo_stack: VecDeque<T>,
+    redo_stack: Vec<T>,
+    max_history: usize,
+    /// `Some` while changes are being merged into the latest undo step, holding the widget that
+    /// had focus when it began
+    ongoing_edit: Option<Option<Id>>,
+}
+
+impl<T: EguiInspect + Clone> Undoable<T> {
+    pub fn new(value: T) -> Self {
+        Self {
+            snapshot: value.clone(),
+            value,
+            undo_stack: VecDeque::new(),
+            redo_stack: vec![],
+            max_history: 100,
+            ongoing_edit: None,
+        }
+    }
+
+    /// Maximum number of undo steps that are kept (defaults to 100)
+    pub fn with_max_history(mut self, max_history: usize) -> Self {
+        self.max_history = max_history;
+        while self.undo_stack.len() > max_history {
+            self.undo_stack.pop_front();
+        }
+        self
+    }
+
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+
+    /// Replaces the value, recording the previous one as an undo step
+    pub fn set(&mut self, value: T) {
+        let previous = std::mem::replace(&mut self.value, value);
+        self.push_undo(previous);
+        self.snapshot = self.value.clone();
+        self.ongoing_edit = None;
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo_stack.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo_stack.is_empty()
+    }
+
+    /// Returns false if there was nothing to undo
+    pub fn undo(&mut self) -> bool {
+        match self.undo_stack.pop_back() {
+            Some(previous) => {
+                self.redo_stack
+                    .push(std::mem::replace(&mut self.value, previous));
+                self.snapshot = self.value.clone();
+                self.ongoing_edit = None;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Returns false if there was nothing to redo
+    pub fn redo(&mut self) -> bool {
+        match self.redo_stack.pop() {
+            Some(next) => {
+                let previous = std::mem::replace(&mut self.value, next);
+                self.undo_stack.push_back(previous);
+                self.snapshot = self.value.clone();
+                self.ongoing_edit = None;
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn clear_history(&mut self) {
+        self.undo_stack.clear();
+        self.redo_stack.clear();
+        self.ongoing_edit = None;
+    }
+
+    fn push_undo(&mut self, previous: T) {
+        self.undo_stack.push_back(previous);
+        if self.undo_stack.len() > self.max_history {
+            self.undo_stack.pop_front();
+        }
+        self.redo_stack.clear();
+    }
+
+    /// Records a change reported by the inner inspect, merging it with the ongoing edit if any
+    fn record_change(&mut self, ui: &egui::Ui) {
+        let focused = ui.memory(|m| m.focused());
+        let merge = match self.ongoing_edit {
+            Some(edit_focus) => {
+                ui.input(|i| i.pointer.any_down()) || (focused.is_some() && focused == edit_focus)
+            }
+            None => false,
+        };
+        if !merge {
+            let previous = std::mem::replace(&mut self.snapshot, self.value.clone());
+            self.push_undo(previous);
+        } else {
+            self.snapshot = self.value.clone();
+        }
+        self.ongoing_edit = Some(focused);
+    }
+
+    /// Ends the ongoing edit once the pointer is released and its widget has lost focus
+    fn update_ongoing_edit(&mut self, ui: &egui::Ui) {
+        if let Some(edit_focus) = self.ongoing_edit {
+            let pointer_down = ui.input(|i| i.pointer.any_down());
+            if !pointer_down && (edit_focus.is_none() || ui.memory(|m| m.focused()) != edit_focus) {
+                self.ongoing_edit = None;
+            }
+        }
+    }
+
+    /// Handles the shortcuts if the pointer is over `rect` (covering the drawn widgets), or the
+    /// focused widget is within it
+    fn handle_shortcuts(&mut self, ui: &egui::Ui, rect: egui::Rect) -> bool {
+        if ui.ctx().wants_keyboard_input() {
+            return false;
+        }
+        let focused_within = ui
+            .memory(|m| m.focused())
+            .and_then(|id| ui.ctx().read_response(id))
+            .is_some_and(|focused| rect.contains_rect(focused.rect));
+        if !focused_within && !ui.rect_contains_pointer(rect) {
+            return false;
+        }
+        let redo = ui.input_mut(|i| REDO_SHORTCUTS.iter().any(|s| i.consume_shortcut(s)));
+        if redo {
+            return self.redo();
+        }
+        let undo = ui.input_mut(|i| i.consume_shortcut(&UNDO_SHORTCUT));
+        undo && self.undo()
+    }
+}
+
+impl<T: EguiInspect + Clone> Deref for Undoable<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.value
+    }
+}
+
+impl<T: EguiInspect + Clone + Default> Default for Undoable<T> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl<T: EguiInspect + Clone> From<T> for Undoable<T> {
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<T: EguiInspect + Clone> EguiInspect for Undoable<T> {
+    fn inspect(&self, label: &str, ui: &mut egui::Ui) {
+        self.value.inspect(label, ui);
+    }
+
+    fn inspect_with(&self, label: &str, ui: &mut egui::Ui, options: &InspectOptions) {
+        self.value.inspect_with(label, ui, options);
+    }
+
+    fn is_inline(&self) -> bool {
+        false
+    }
+
+    fn matches_filter(&self, query: &str) -> bool {
+        self.value.matches_filter(query)
+    }
+
+    fn validation_errors(&self) -> Vec<ValidationError> {
+        self.value.validation_errors()
+    }
+
+    fn inspect_diff(&self, other: &Self, label: &str, ui: &mut egui::Ui) -> bool {
+        self.value.inspect_diff(&other.value, label, ui)
+    }
+
+    fn inspect_mut(&mut self, label: &str, ui: &mut egui::Ui) -> Response {
+        self.inspect_mut_with(label, ui, &InspectOptions::default())
+    }
+
+    fn inspect_mut_with(
+        &mut self,
+        label: &str,
+        ui: &mut egui::Ui,
+        options: &InspectOptions,
+    ) -> Response {
+        let mut history_changed = false;
+        let buttons = ui.horizontal(|ui| {
+            let undo = ui
+                .add_enabled(self.can_undo(), egui::Button::new("Undo"))
+                .on_hover_text(format!("{} steps (Ctrl+Z)", self.undo_stack.len()));
+            if undo.clicked() {
+                history_changed |= self.undo();
+            }
+            let redo = ui
+                .add_enabled(self.can_redo(), egui::Button::new("Redo"))
+                .on_hover_text(format!(
+                    "{} steps (Ctrl+Shift+Z / Ctrl+Y)",
+                    self.redo_stack.len()
+                ));
+            if redo.clicked() {
+                history_changed |= self.redo();
+            }
+        });
+
+        let mut response = self.value.inspect_mut_with(label, ui, options);
+        if response.changed() {
+            self.record_change(ui);
+        }
+        self.update_ongoing_edit(ui);
+
+        // NOTE: handled once the value is drawn, to know where it is, so it is only shown as
+        // restored in the next frame
+        if self.handle_shortcuts(ui, buttons.response.rect.union(response.rect)) {
+            history_changed = true;
+            ui.ctx().request_repaint();
+        }
+
+        if history_changed {
+            response.mark_changed();
+        }
+        response
+    }
+}
+
+#[test]
+fn undo_redo_restores_values() {
+    let mut value = Undoable::new(1u32).with_max_history(2);
+    value.set(2);
+    value.set(3);
+    value.set(4);
+    assert!(value.undo());
+    assert!(value.undo());
+    assert_eq!(*value, 2);
+    // history was bounded to two steps
+    assert!(!value.undo());
+    assert!(value.redo());
+    assert_eq!(*value, 3);
+    value.set(5);
+    assert!(!value.can_redo());
+}
+
+#[test]
+fn history_is_bounded() {
+    let mut value = Undoable::new(0u32);
+    for i in 1..=5 {
+        value.set(i);
+    }
+    // the oldest steps are dropped when the bound is lowered
+    let mut value = value.with_max_history(3);
+    value.set(6);
+    let mut undone = 0;
+    while value.undo() {
+        undone += 1;
+    }
+    assert_eq!(undone, 3);
+    assert_eq!(*value, 3);
+}
+
+#[test]
+fn drags_are_merged_into_one_step() {
+    use crate::testing::Harness;
+
+    let mut harness = Harness::new(Undoable::new(0u32)).with_label("n");
+    assert!(harness.drag("n", egui::vec2(40.0, 0.0)));
+    assert!(*harness.value > 0);
+    assert_eq!(harness.value.undo_stack.len(), 1);
+
+    // a separate drag is a separate step
+    assert!(harness.drag("n", egui::vec2(40.0, 0.0)));
+    assert_eq!(harness.value.undo_stack.len(), 2);
+    assert!(harness.click("Undo"));
+    assert!(harness.click("Undo"));
+    assert_eq!(*harness.value, 0);
+}
+
+#[test]
+fn shortcuts_apply_to_hovered_undoable() {
+    use crate as egui_inspect;
+    use crate::testing::Harness;
+    use egui_inspect_derive::EguiInspect;
+
+    #[derive(EguiInspect)]
+    struct Pair {
+        a: Undoable<u32>,
+        b: Undoable<u32>,
+    }
+
+    let mut pair = Pair {
+        a: Undoable::new(0),
+        b: Undoable::new(0),
+    };
+    pair.a.set(1);
+    pair.b.set(1);
+    let mut harness = Harness::new(pair);
+    let b = harness.get("b").rect.center();
+    harness.input(vec![vec![egui::Event::PointerMoved(b)]]);
+    assert!(harness.key_press(Key::Z, Modifiers::COMMAND));
+    assert_eq!((*harness.value.a, *harness.value.b), (1, 0));
+}
@@ -1,6 +1,9 @@
 use std::any::type_name;
 
-use egui::{text::LayoutJob, Align, FontSelection, RichText, Style};
+use egui::{
+    text::LayoutJob, Align, CollapsingResponse, FontSelection, Rect, Response, RichText, Sense,
+    Style, Ui,
+};
 
 pub fn concat_rich_text(rtv: impl IntoIterator<Item = RichText>) -> LayoutJob {
     let style = Style::default();
@@ -24,6 +27,26 @@ pub fn type_name_base<T>() -> &'static str {
     name
 }
 
+/// A response covering nothing, without allocating any space in `ui`. Used as the starting point
+/// when aggregating the responses of a (possibly empty) sequence of inspected values.
+pub fn empty_response(ui: &Ui) -> Response {
+    ui.interact(
+        Rect::NOTHING,
+        ui.id().with("empty_response"),
+        Sense::hover(),
+    )
+}
+
+/// Merges the header response of a collapsing section with the (aggregated) response returned
+/// by its body, if it was open.
+pub fn collapsing_response(collapsing: CollapsingResponse<Response>) -> Response {
+    let mut response = collapsing.header_response;
+    if let Some(body) = collapsing.body_returned {
+        response |= body;
+    }
+    response
+}
+
 #[test]
 fn concat_rich_text_accepts_vec() {
     concat_rich_text(vec![
@@ -0,0 +1,104 @@
+//! Support for the `validate` attribute of the [crate::EguiInspect] derive.
+//!
+//! A validation function takes a reference to the value and returns `Result<(), String>`, e.g.
+//! ```
+//! # use egui_inspect::*;
+//! fn positive(x: &f32) -> Result<(), String> {
+//!     match *x > 0.0 {
+//!         true => Ok(()),
+//!         false => Err("must be positive".into()),
+//!     }
+//! }
+//!
+//! #[derive(EguiInspect, Default)]
+//! struct Params {
+//!     #[inspect(validate = "positive")]
+//!     gain: f32,
+//! }
+//!
+//! fn params_ui(params: &mut Params, ui: &mut egui::Ui) {
+//!     params.inspect_mut("params", ui);
+//!     if ui.add_enabled(params.is_valid(), egui::Button::new("Apply")).clicked() {
+//!         // ...
+//!     }
+//! }
+//! ```
+//! Invalid fields are outlined in red with the message shown below them, and their errors are
+//! collected by [crate::EguiInspect::validation_errors] of the enclosing values.
+
+use std::fmt::{self, Display};
+
+use egui::{CornerRadius, Rect, Stroke, StrokeKind, Ui};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ValidationError {
+    /// Path of the invalid value relative to the value that was validated, e.g. `mode.p` or
+    /// `items[2]` (empty if the value itself is invalid)
+    pub path: String,
+    pub message: String,
+}
+
+impl ValidationError {
+    pub fn new(path: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            message: message.into(),
+        }
+    }
+
+    /// Prepends a field name (or an index, as `[i]`) to the path of the error
+    pub fn prefixed(mut self, prefix: &str) -> Self {
+        self.path = match (prefix.is_empty(), self.path.is_empty()) {
+            (true, _) => self.path,
+            (false, true) => prefix.to_string(),
+            (false, false) if self.path.starts_with('[') => format!("{prefix}{}", self.path),
+            (false, false) => format!("{prefix}.{}", self.path),
+        };
+        self
+    }
+}
+
+impl Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.path.is_empty() {
+            true => write!(f, "{}", self.message),
+            false => write!(f, "{}: {}", self.path, self.message),
+        }
+    }
+}
+
+/// Prefixes each error with `prefix` (see [ValidationError::prefixed])
+pub fn prefix_errors(errors: Vec<ValidationError>, prefix: &str) -> Vec<ValidationError> {
+    errors.into_iter().map(|e| e.prefixed(prefix)).collect()
+}
+
+/// Outlines `rect` (the area of the invalid value) in red, and shows `message` below it
+pub fn show_validation_error(ui: &mut Ui, rect: Rect, message: &str) {
+    let color = ui.visuals().error_fg_color;
+    ui.painter().rect_stroke(
+        rect.expand(2.0),
+        CornerRadius::same(2),
+        Stroke::new(1.0, color),
+        StrokeKind::Outside,
+    );
+    ui.colored_label(color, message);
+}
+
+/// Lists `errors`, e.g. next to a disabled "Apply" button
+pub fn show_validation_errors(ui: &mut Ui, errors: &[ValidationError]) {
+    let color = ui.visuals().error_fg_color;
+    for error in errors {
+        ui.colored_label(color, error.to_string());
+    }
+}
+
+#[test]
+fn error_paths_are_joined() {
+    let e = ValidationError::new("", "too small")
+        .prefixed("p")
+        .prefixed("[3]")
+        .prefixed("items")
+        .prefixed("");
+    assert_eq!(e.path, "items[3].p");
+    assert_eq!(e.to_string(), "items[3].p: too small");
+}
@@ -0,0 +1,394 @@
+//! Editor of the elements of sequences ([Vec] and [std::collections::VecDeque]). Each element has
+//! a drag handle (☰) to move it elsewhere in the list, a checkbox selecting it for a bulk delete,
+//! and a menu (⋯) to insert an element above or below it, move it, remove it, or duplicate it.
+//! The selection is cleared whenever elements are inserted, moved or removed.
+//!
+//! Duplicating requires `Clone` elements, so it is offered for fields annotated with
+//! `#[inspect(duplicate)]`:
+//! ```
+//! # use egui_inspect::*;
+//! #[derive(EguiInspect, Clone, Default)]
+//! struct Waypoint {
+//!     x: f32,
+//!     y: f32,
+//! }
+//!
+//! #[derive(EguiInspect)]
+//! struct Route {
+//!     #[inspect(duplicate)]
+//!     waypoints: Vec<Waypoint>,
+//! }
+//! ```
+
+use std::collections::{BTreeSet, VecDeque};
+
+use egui::{Id, Response, Ui, WidgetInfo, WidgetType};
+
+use crate::base_type_inspect::show_collapsing;
+use crate::filter::{contains_match, force_open, show_field};
+use crate::utils::{collapsing_response, empty_response};
+use crate::widget_state::{load_state, store_state};
+use crate::{EguiInspect, InspectOptions};
+
+/// Sequences which can be edited by [sequence_inspect_mut]
+pub trait Sequence {
+    type Item;
+    fn len(&self) -> usize;
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    fn item(&self, i: usize) -> &Self::Item;
+    fn item_mut(&mut self, i: usize) -> &mut Self::Item;
+    fn insert(&mut self, i: usize, item: Self::Item);
+    fn remove(&mut self, i: usize) -> Self::Item;
+    fn clear(&mut self);
+}
+
+impl<T> Sequence for Vec<T> {
+    type Item = T;
+    fn len(&self) -> usize {
+        self.len()
+    }
+    fn item(&self, i: usize) -> &T {
+        &self[i]
+    }
+    fn item_mut(&mut self, i: usize) -> &mut T {
+        &mut self[i]
+    }
+    fn insert(&mut self, i: usize, item: T) {
+        self.insert(i, item)
+    }
+    fn remove(&mut self, i: usize) -> T {
+        self.remove(i)
+    }
+    fn clear(&mut self) {
+        self.clear()
+    }
+}
+
+impl<T> Sequence for VecDeque<T> {
+    type Item = T;
+    fn len(&self) -> usize {
+        self.len()
+    }
+    fn item(&self, i: usize) -> &T {
+        &self[i]
+    }
+    fn item_mut(&mut self, i: usize) -> &mut T {
+        &mut self[i]
+    }
+    fn insert(&mut self, i: usize, item: T) {
+        self.insert(i, item)
+    }
+    fn remove(&mut self, i: usize) -> T {
+        self.remove(i).expect("index within the deque")
+    }
+    fn clear(&mut self) {
+        self.clear()
+    }
+}
+
+/// Copies an element, e.g. [Clone::clone]
+pub type Duplicate<T> = fn(&T) -> T;
+
+/// A change made through the controls of the editor, applied once all elements are drawn
+enum Edit {
+    Insert(usize),
+    Duplicate(usize),
+    Move { from: usize, to: usize },
+    Remove(usize),
+    RemoveSelected,
+    Clear,
+}
+
+/// Payload of a dragged element, tagged with its list so that it is not dropped into another one
+struct Dragged {
+    list: Id,
+    index: usize,
+}
+
+/// Read-only list of the elements of `seq`, drawn with the `options`
+pub fn sequence_inspect<S>(seq: &S, label: &str, ui: &mut Ui, options: &InspectOptions)
+where
+    S: Sequence + EguiInspect,
+    S::Item: EguiInspect,
+{
+    let open = force_open(ui, seq);
+    show_collapsing(ui, label, "", open, |ui| {
+        for i in 0..seq.len() {
+            let item = seq.item(i);
+            let item_label = format!("{label}[{i}]");
+            let contains_match = contains_match(ui, item);
+            show_field(ui, &item_label, contains_match, |ui| {
+                ui.push_id(i, |ui| item.inspect_with(&item_label, ui, options));
+            });
+        }
+    });
+}
+
+/// Editor (see [self]) of the elements of `seq`, new elements are defaults, and elements are
+/// duplicated by `duplicate` when given
+pub fn sequence_inspect_mut<S>(
+    seq: &mut S,
+    label: &str,
+    ui: &mut Ui,
+    options: &InspectOptions,
+    duplicate: Option<Duplicate<S::Item>>,
+) -> Response
+where
+    S: Sequence + EguiInspect,
+    S::Item: EguiInspect + Default,
+{
+    let open = force_open(ui, seq);
+    collapsing_response(show_collapsing(ui, label, "", open, |ui| {
+        let list = ui.id();
+        let selection_id = list.with("selection");
+        let mut selection: BTreeSet<usize> = load_state(ui, selection_id);
+        let mut response = empty_response(ui);
+        let mut edit = None;
+        let n = seq.len();
+        for i in 0..n {
+            let item = seq.item_mut(i);
+            let item_label = format!("{label}[{i}]");
+            let contains_match = contains_match(ui, item);
+            show_field(ui, &item_label, contains_match, |ui| {
+                let row = ui.push_id(i, |ui| {
+                    ui.horizontal_top(|ui| {
+                        let controls = Row {
+                            list,
+                            index: i,
+                            len: n,
+                            label: &item_label,
+                            can_duplicate: duplicate.is_some(),
+                        };
+                        if let Some(row_edit) = controls.show(&mut selection, ui) {
+                            edit = Some(row_edit);
+                        }
+                        response |= item.inspect_mut_with(&item_label, ui, options);
+                    })
+                    .response
+                });
+                if let Some(dropped) = drop_position(&row.inner, list, i) {
+                    edit = Some(dropped);
+                }
+            });
+        }
+
+        ui.horizontal(|ui| {
+            if ui.button("Push default").clicked() {
+                edit = Some(Edit::Insert(n));
+            }
+            let selected = selection.len();
+            if selected > 0 && ui.button(format!("Remove selected ({selected})")).clicked() {
+                edit = Some(Edit::RemoveSelected);
+            }
+            if n > 0 && ui.button("Clear").clicked() {
+                edit = Some(Edit::Clear);
+            }
+        });
+
+        if let Some(edit) = edit {
+            apply(seq, edit, &selection, duplicate);
+            selection.clear();
+            response.mark_changed();
+        }
+        store_state(ui, selection_id, selection);
+        response
+    }))
+}
+
+/// Controls in front of an element: drag handle, selection checkbox and menu
+struct Row<'a> {
+    list: Id,
+    index: usize,
+    len: usize,
+    label: &'a str,
+    can_duplicate: bool,
+}
+
+impl Row<'_> {
+    fn show(&self, selection: &mut BTreeSet<usize>, ui: &mut Ui) -> Option<Edit> {
+        let i = self.index;
+        let payload = Dragged {
+            list: self.list,
+            index: i,
+        };
+        ui.dnd_drag_source(ui.id().with("handle"), payload, |ui| ui.label("☰"))
+            .response
+            .on_hover_text("Drag to move");
+
+        let mut selected = selection.contains(&i);
+        let checkbox = ui.add(egui::Checkbox::without_text(&mut selected));
+        checkbox.widget_info(|| {
+            let text = format!("select {}", self.label);
+            WidgetInfo::selected(WidgetType::Checkbox, true, selected, text)
+        });
+        if checkbox.changed() {
+            match selected {
+                true => selection.insert(i),
+                false => selection.remove(&i),
+            };
+        }
+
+        let mut edit = None;
+        ui.menu_button("⋯", |ui| {
+            let up = i.saturating_sub(1);
+            let actions = [
+                ("Insert above", true, Edit::Insert(i)),
+                ("Insert below", true, Edit::Insert(i + 1)),
+                ("Duplicate", self.can_duplicate, Edit::Duplicate(i)),
+                ("Move up", i > 0, Edit::Move { from: i, to: up }),
+                (
+                    "Move down",
+                    i + 1 < self.len,
+                    Edit::Move { from: i, to: i + 1 },
+                ),
+                ("Remove", true, Edit::Remove(i)),
+            ];
+            for (text, enabled, action) in actions {
+                if matches!(action, Edit::Remove(_)) {
+                    ui.separator();
+                }
+                if ui.add_enabled(enabled, egui::Button::new(text)).clicked() {
+                    edit = Some(action);
+                    ui.close();
+                }
+            }
+        });
+        edit
+    }
+}
+
+/// Move of the element of the list dropped onto `row` (of element `i`), above or below it
+/// depending on where it is dropped, also marks the position while it is dragged over
+fn drop_position(row: &Response, list: Id, i: usize) -> Option<Edit> {
+    let below = |pos: Option<egui::Pos2>| pos.is_some_and(|pos| pos.y > row.rect.center().y);
+    let pointer = row.ctx.pointer_latest_pos();
+    if let Some(dragged) = row.dnd_hover_payload::<Dragged>() {
+        if dragged.list == list {
+            let y = match below(pointer) {
+                true => row.rect.bottom(),
+                false => row.rect.top(),
+            };
+            let stroke = row.ctx.style().visuals.selection.stroke;
+            let painter = row.ctx.layer_painter(row.layer_id);
+            painter.hline(row.rect.x_range(), y, stroke);
+        }
+    }
+    let dragged = row.dnd_release_payload::<Dragged>()?;
+    // the position is that before the dragged element is taken out
+    let to = i + below(pointer) as usize;
+    let to = match to > dragged.index {
+        true => to - 1,
+        false => to,
+    };
+    (dragged.list == list && to != dragged.index).then_some(Edit::Move {
+        from: dragged.index,
+        to,
+    })
+}
+
+fn apply<S>(
+    seq: &mut S,
+    edit: Edit,
+    selection: &BTreeSet<usize>,
+    duplicate: Option<Duplicate<S::Item>>,
+) where
+    S: Sequence,
+    S::Item: Default,
+{
+    match edit {
+        Edit::Insert(i) => seq.insert(i, S::Item::default()),
+        Edit::Duplicate(i) => {
+            if let Some(duplicate) = duplicate {
+                let copy = duplicate(seq.item(i));
+                seq.insert(i + 1, copy);
+            }
+        }
+        Edit::Move { from, to } => {
+            let item = seq.remove(from);
+            seq.insert(to, item);
+        }
+        Edit::Remove(i) => {
+            seq.remove(i);
+        }
+        Edit::RemoveSelected => {
+            // from the end, so that the indices of the remaining selected elements stay valid
+            for &i in selection.iter().rev() {
+                if i < seq.len() {
+                    seq.remove(i);
+                }
+            }
+        }
+        Edit::Clear => seq.clear(),
+    }
+}
+
+/// [sequence_inspect_mut] offering to duplicate elements, for fields annotated with
+/// `#[inspect(duplicate)]`
+pub fn inspect_mut_duplicating<S>(
+    seq: &mut S,
+    label: &str,
+    ui: &mut Ui,
+    options: &InspectOptions,
+) -> Response
+where
+    S: Sequence + EguiInspect,
+    S::Item: EguiInspect + Default + Clone,
+{
+    sequence_inspect_mut(seq, label, ui, options, Some(S::Item::clone))
+}
+
+#[test]
+fn elements_are_inserted_selected_and_dragged() {
+    use crate::testing::Harness;
+
+    let mut harness = Harness::new(VecDeque::from([1u8, 2, 3])).with_label("q");
+    harness.click("q");
+    harness.click("⋯");
+    assert!(harness.click("Insert below"));
+    assert_eq!(harness.value, [1, 0, 2, 3]);
+
+    harness.click("select q[1]");
+    harness.click("select q[3]");
+    assert!(harness.click("Remove selected (2)"));
+    assert_eq!(harness.value, [1, 2]);
+    assert!(harness.query("Remove selected (0)").is_none());
+
+    // dropped onto the lower half of the second element
+    let handles: Vec<_> = harness
+        .nodes()
+        .iter()
+        .filter(|node| {
+            node.role == egui::accesskit::Role::Label && node.value.as_deref() == Some("☰")
+        })
+        .map(|node| node.rect.center())
+        .collect();
+    let delta = handles[1] - handles[0] + egui::vec2(0.0, 4.0);
+    assert!(harness.drag_at(handles[0], delta));
+    assert_eq!(harness.value, [2, 1]);
+
+    assert!(harness.click("Clear"));
+    assert!(harness.value.is_empty());
+}
+
+#[test]
+fn elements_are_duplicated() {
+    use crate as egui_inspect;
+    use egui_inspect_derive::EguiInspect;
+
+    #[derive(EguiInspect)]
+    struct Route {
+        #[inspect(duplicate)]
+        stops: Vec<String>,
+    }
+
+    let route = Route {
+        stops: vec!["depot".to_string(), "market".to_string()],
+    };
+    let mut harness = crate::testing::Harness::new(route);
+    harness.click("stops");
+    harness.click("⋯");
+    assert!(harness.click("Duplicate"));
+    assert_eq!(harness.value.stops, ["depot", "depot", "market"]);
+}
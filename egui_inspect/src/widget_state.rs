@@ -0,0 +1,50 @@
+//! Storage for the transient ui state of inspect impls (search queries, text buffers, ...). The
+//! state is kept in [egui::Memory] under a widget [Id], rather than inside the inspected value or
+//! in globals shared between all instances.
+//!
+//! Ids are best derived from the [egui::Ui] the value is drawn in, e.g. `ui.id().with("query")`,
+//! which is unique per inspected value as long as nested values are given their own id scopes
+//! (see [crate::EguiInspect]).
+
+use egui::{Id, Ui};
+
+/// Copy of the state stored under `id`, or its default if nothing has been stored yet
+pub fn load_state<S: 'static + Clone + Default>(ui: &Ui, id: Id) -> S {
+    ui.data(|d| d.get_temp(id)).unwrap_or_default()
+}
+
+pub fn store_state<S: 'static + Clone + Send + Sync>(ui: &Ui, id: Id, state: S) {
+    ui.data_mut(|d| d.insert_temp(id, state));
+}
+
+pub fn clear_state<S: 'static + Default>(ui: &Ui, id: Id) {
+    ui.data_mut(|d| d.remove_temp::<S>(id));
+}
+
+/// Runs `f` on the state stored under `id` and writes it back afterwards. The state is copied out
+/// of [egui::Memory] for the duration of `f`, so `f` is free to draw widgets with the passed `ui`.
+pub fn with_state<S: 'static + Clone + Default + Send + Sync, R>(
+    ui: &mut Ui,
+    id: Id,
+    f: impl FnOnce(&mut S, &mut Ui) -> R,
+) -> R {
+    let mut state = load_state(ui, id);
+    let ret = f(&mut state, ui);
+    store_state(ui, id, state);
+    ret
+}
+
+#[test]
+fn state_is_kept_per_id() {
+    let ctx = egui::Context::default();
+    let _ = ctx.run(Default::default(), |ctx| {
+        egui::CentralPanel::default().show(ctx, |ui| {
+            let (a, b) = (ui.id().with("a"), ui.id().with("b"));
+            with_state(ui, a, |s: &mut String, _| s.push_str("edited"));
+            assert_eq!(load_state::<String>(ui, a), "edited");
+            assert_eq!(load_state::<String>(ui, b), "");
+            clear_state::<String>(ui, a);
+            assert_eq!(load_state::<String>(ui, a), "");
+        });
+    });
+}
@@ -0,0 +1,11 @@
+use egui_inspect::EguiInspect;
+
+#[derive(EguiInspect)]
+struct Config {
+    #[inspect(custom_func_mut = "draw gain")]
+    gain: f32,
+    #[inspect(visible_if = "*gain >")]
+    offset: f32,
+}
+
+fn main() {}
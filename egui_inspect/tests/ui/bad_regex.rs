@@ -0,0 +1,11 @@
+use egui_inspect::EguiInspect;
+
+#[derive(EguiInspect)]
+struct Account {
+    #[inspect(regex = "[a-z]+(_[a-z]+")]
+    user: String,
+    #[inspect(regex = r"\d+\q")]
+    pin: String,
+}
+
+fn main() {}
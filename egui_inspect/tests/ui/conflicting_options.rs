@@ -0,0 +1,24 @@
+use egui_inspect::EguiInspect;
+
+#[derive(EguiInspect)]
+struct Config {
+    #[inspect(slider, log_slider, min = 2, max = 1)]
+    level: u8,
+    #[inspect(button, format = "hex")]
+    gain: f32,
+    #[inspect(max = 256, step = 0)]
+    byte: u8,
+    #[inspect(code = "glsl", password)]
+    shader: String,
+    #[inspect(markdown, code = "rust")]
+    notes: String,
+}
+
+#[derive(EguiInspect)]
+#[inspect(radio, tabs)]
+enum Mode {
+    Fast,
+    Slow,
+}
+
+fn main() {}
@@ -0,0 +1,40 @@
+// Derives using code options with groups (parentheses, braces), one after another
+use egui_inspect::EguiInspect;
+
+// NOTE: in a module, to not run the generated `main`
+#[allow(dead_code)]
+mod app {
+    use egui_inspect::{eframe, EframeMain, EguiInspect};
+
+    #[derive(EguiInspect, EframeMain)]
+    #[eframe_main(init = Viewer::new(cc))]
+    struct Viewer {
+        on: bool,
+        #[inspect(visible_if = matches!(on, true))]
+        x: u8,
+    }
+
+    impl Viewer {
+        fn new(cc: &eframe::CreationContext) -> Self {
+            Self {
+                on: cc.egui_ctx.zoom_factor() > 0.0,
+                x: 0,
+            }
+        }
+    }
+}
+
+#[derive(EguiInspect)]
+enum Source {
+    #[inspect(ctor = Source::File { path: String::from("a"), retries: 1 })]
+    File { path: String, retries: u8 },
+    #[inspect(ctor = Source::Stdin)]
+    Stdin,
+}
+
+#[derive(EguiInspect, Default)]
+struct Plain {
+    y: u8,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use egui_inspect::EguiInspect;
+
+#[derive(EguiInspect)]
+#[inspect(radio)]
+struct Config {
+    gain: f32,
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+use egui_inspect::EguiInspect;
+
+#[derive(EguiInspect)]
+struct Config {
+    #[inspect(slider, min = 0.0, max = 1.0)]
+    name: String,
+    #[inspect(multiline, password, regex = "[0-9]+")]
+    count: u32,
+    #[inspect(duplicate)]
+    enabled: bool,
+}
+
+fn main() {}
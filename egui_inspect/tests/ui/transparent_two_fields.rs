@@ -0,0 +1,7 @@
+use egui_inspect::EguiInspect;
+
+#[derive(EguiInspect)]
+#[inspect(transparent)]
+struct Pair(f32, f32);
+
+fn main() {}
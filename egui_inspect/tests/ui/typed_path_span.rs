@@ -0,0 +1,10 @@
+use egui_inspect::EguiInspect;
+
+#[derive(EguiInspect)]
+#[inspect(frame_style = crate::MISSING_STYLE)]
+struct Config {
+    #[inspect(custom_func_mut = draw_gian)]
+    gain: f32,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use egui_inspect::EguiInspect;
+
+#[derive(EguiInspect)]
+union Bits {
+    int: u32,
+    float: f32,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use egui_inspect::EguiInspect;
+
+#[derive(EguiInspect)]
+struct Config {
+    #[inspect(sldier)]
+    gain: f32,
+}
+
+fn main() {}
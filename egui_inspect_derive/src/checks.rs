@@ -0,0 +1,297 @@
+use darling::{Error, FromField, FromVariant};
+use syn::spanned::Spanned;
+use syn::{Attribute, Data, DeriveInput, Field, Fields, Meta, NestedMeta};
+
+use crate::internal_paths::path_is_internally_handled;
+use crate::utils::get_path_str;
+use crate::{DeriveAttr, FieldAttr, Number, NumberFormat, VariantAttr};
+
+/// Checks the `#[inspect]` attributes of the input, reporting all errors at once (with the span of
+/// the offending attribute, or item), so that code generation can assume they are valid
+pub(crate) fn check_input(input: &DeriveInput, attr: &DeriveAttr) -> darling::Result<()> {
+    let mut errors = Error::accumulator();
+    let selectors = [
+        ("radio", attr.radio),
+        ("tabs", attr.tabs),
+        ("segmented", attr.segmented),
+    ];
+    match &input.data {
+        Data::Struct(data) => {
+            for (option, set) in selectors {
+                if set {
+                    errors.push(
+                        Error::custom(format!("`{option}` only applies to enums"))
+                            .with_span(&option_span(&input.attrs, option, input)),
+                    );
+                }
+            }
+            if attr.transparent {
+                check_transparent(&data.fields, input, &mut errors);
+            }
+            check_fields(&data.fields, &mut errors);
+        }
+        Data::Enum(data_enum) => {
+            let mut set = selectors.iter().filter(|(_, set)| *set).skip(1);
+            if let Some((option, _)) = set.next() {
+                errors.push(
+                    Error::custom("only one of `radio`, `tabs` and `segmented` may be used")
+                        .with_span(&option_span(&input.attrs, option, input)),
+                );
+            }
+            if attr.transparent {
+                errors.push(
+                    Error::custom("`transparent` only applies to structs").with_span(&option_span(
+                        &input.attrs,
+                        "transparent",
+                        input,
+                    )),
+                );
+            }
+            for variant in &data_enum.variants {
+                let variant_attr = errors.handle(VariantAttr::from_variant(variant));
+                if let Some(ctor) = variant_attr.and_then(|a| a.ctor) {
+                    let span = option_span(&variant.attrs, "ctor", variant);
+                    check_expr(&ctor, "ctor", &span, &mut errors);
+                }
+                check_fields(&variant.fields, &mut errors);
+            }
+        }
+        Data::Union(data_union) => errors.push(
+            Error::custom("EguiInspect cannot be derived for unions")
+                .with_span(&data_union.union_token),
+        ),
+    }
+
+    for (option, path) in [
+        ("validate", &attr.validate),
+        ("frame_style", &attr.frame_style),
+    ] {
+        if let Some(path) = path {
+            let span = option_span(&input.attrs, option, input);
+            check_path(path, option, &span, &mut errors);
+        }
+    }
+    errors.finish()
+}
+
+/// Span of `option` within the `#[inspect]` attributes among `attrs` (that of the item if absent)
+fn option_span(attrs: &[Attribute], option: &str, item: &impl Spanned) -> proc_macro2::Span {
+    attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("inspect"))
+        .filter_map(|attr| match attr.parse_meta() {
+            Ok(Meta::List(list)) => Some(list.nested),
+            _ => None,
+        })
+        .flatten()
+        .find_map(|nested| match nested {
+            NestedMeta::Meta(meta) if meta.path().is_ident(option) => Some(meta.span()),
+            _ => None,
+        })
+        .unwrap_or_else(|| item.span())
+}
+
+fn check_transparent(
+    fields: &Fields,
+    input: &DeriveInput,
+    errors: &mut darling::error::Accumulator,
+) {
+    let shown = fields
+        .iter()
+        .filter(|f| FieldAttr::from_field(f).map_or(true, |attr| !attr.hide))
+        .count();
+    if shown != 1 {
+        errors.push(
+            Error::custom("`transparent` structs need exactly one field which is not hidden")
+                .with_span(&input.ident),
+        );
+    }
+}
+
+fn check_fields(fields: &Fields, errors: &mut darling::error::Accumulator) {
+    for f in fields {
+        if let Some(attr) = errors.handle(FieldAttr::from_field(f)) {
+            check_field(f, &attr, errors);
+        }
+    }
+}
+
+/// Checks the options of a field against each other and against the field's type
+fn check_field(f: &Field, attr: &FieldAttr, errors: &mut darling::error::Accumulator) {
+    let error = |option: &str, message: &str| {
+        Error::custom(message).with_span(&option_span(&f.attrs, option, f))
+    };
+
+    // only types drawn by this crate are known, anything else may accept any option
+    let path_str = get_path_str(&f.ty).filter(path_is_internally_handled);
+    let is_number = path_str
+        .as_deref()
+        .is_some_and(|p| !matches!(p, "bool" | "String" | "str"));
+    let is_text = path_str
+        .as_deref()
+        .is_some_and(|p| matches!(p, "String" | "str"));
+    let number_options = [
+        ("slider", attr.slider),
+        ("log_slider", attr.log_slider),
+        ("min", attr.min.is_some()),
+        ("max", attr.max.is_some()),
+        ("step", attr.step.is_some()),
+        ("speed", attr.speed.is_some()),
+        ("prefix", attr.prefix.is_some()),
+        ("suffix", attr.suffix.is_some()),
+        ("decimals", attr.decimals.is_some()),
+        ("clamp", attr.clamp.is_some()),
+        ("format", attr.format.is_some()),
+    ];
+    for (option, set) in number_options {
+        if set && path_str.is_some() && !is_number {
+            errors.push(error(
+                option,
+                &format!("`{option}` only applies to numbers"),
+            ));
+        }
+    }
+    let text_options = [
+        ("multiline", attr.multiline),
+        ("password", attr.password),
+        ("hint", attr.hint.is_some()),
+        ("max_len", attr.max_len.is_some()),
+        ("charset", attr.charset.is_some()),
+        ("regex", attr.regex.is_some()),
+        ("code", attr.code.is_some()),
+        ("markdown", attr.markdown),
+    ];
+    for (option, set) in text_options {
+        if set && path_str.is_some() && !is_text {
+            errors.push(error(
+                option,
+                &format!("`{option}` only applies to strings"),
+            ));
+        }
+    }
+    // the code and Markdown editors are plain multiline edits
+    for (editor, set, others) in [
+        ("code", attr.code.is_some(), &text_options[1..6]),
+        ("markdown", attr.markdown, &text_options[1..7]),
+    ] {
+        for (option, _) in others.iter().filter(|(_, other)| set && *other) {
+            errors.push(error(
+                option,
+                &format!("`{option}` cannot be combined with `{editor}`"),
+            ));
+        }
+    }
+    // NOTE: parsed as by the regex crate, which egui_inspect::pattern matches with
+    if let Some(regex) = &attr.regex {
+        if let Err(e) = regex_syntax::Parser::new().parse(regex) {
+            errors.push(error("regex", &format!("`regex` is not a valid pattern, {e}")));
+        }
+    }
+    if attr.duplicate && path_str.is_some() {
+        errors.push(error(
+            "duplicate",
+            "`duplicate` only applies to `Vec` and `VecDeque` fields",
+        ));
+    }
+    if attr.button && path_str.as_deref().is_some_and(|p| p != "bool") {
+        errors.push(error("button", "`button` only applies to `bool` fields"));
+    }
+    if attr.slider && attr.log_slider {
+        errors.push(error(
+            "log_slider",
+            "`slider` and `log_slider` cannot be combined",
+        ));
+    }
+    if let (Some(min), Some(max)) = (attr.min, attr.max) {
+        if min.greater_than(max) {
+            errors.push(error("min", "`min` is greater than `max`"));
+        }
+    }
+    let is_float = path_str
+        .as_deref()
+        .is_some_and(|p| matches!(p, "f32" | "f64"));
+    if is_float && matches!(attr.format, Some(NumberFormat::Hex | NumberFormat::Binary)) {
+        errors.push(error("format", "`hex` and `binary` only apply to integers"));
+    }
+    if let Some((type_min, type_max)) = path_str.as_deref().and_then(integer_range) {
+        for (option, bound) in [("min", attr.min), ("max", attr.max)] {
+            if let Some(Number::Int(i)) = bound {
+                if i < type_min || i > type_max {
+                    let message = format!("`{option}` is out of the range of the field's type");
+                    errors.push(error(option, &message));
+                }
+            }
+        }
+    }
+    if attr
+        .step
+        .is_some_and(|step| !step.greater_than(Number::Int(0)))
+    {
+        errors.push(error("step", "`step` must be positive"));
+    }
+    let custom = attr.custom_func.is_some() || attr.custom_func_mut.is_some();
+    if attr.flatten && custom {
+        errors.push(error(
+            "flatten",
+            "`flatten` cannot be combined with a custom function",
+        ));
+    }
+
+    for (option, path) in [
+        ("custom_func", &attr.custom_func),
+        ("custom_func_mut", &attr.custom_func_mut),
+        ("validate", &attr.validate),
+    ] {
+        if let Some(path) = path {
+            check_path(path, option, &option_span(&f.attrs, option, f), errors);
+        }
+    }
+    for (option, expr) in [
+        ("visible_if", &attr.visible_if),
+        ("enabled_if", &attr.enabled_if),
+    ] {
+        if let Some(expr) = expr {
+            check_expr(expr, option, &option_span(&f.attrs, option, f), errors);
+        }
+    }
+}
+
+fn check_path(
+    path: &str,
+    option: &str,
+    span: &proc_macro2::Span,
+    errors: &mut darling::error::Accumulator,
+) {
+    if syn::parse_str::<syn::Path>(path).is_err() {
+        errors.push(Error::custom(format!("`{option}` is not a path: {path:?}")).with_span(span));
+    }
+}
+
+fn check_expr(
+    expr: &str,
+    option: &str,
+    span: &proc_macro2::Span,
+    errors: &mut darling::error::Accumulator,
+) {
+    if let Err(e) = syn::parse_str::<syn::Expr>(expr) {
+        errors.push(
+            Error::custom(format!("`{option}` is not an expression ({e}): {expr:?}"))
+                .with_span(span),
+        );
+    }
+}
+
+/// Range of the integer type `path` (`usize` and `isize` taken as 64 bits)
+fn integer_range(path: &str) -> Option<(i128, i128)> {
+    Some(match path {
+        "u8" => (0, u8::MAX.into()),
+        "i8" => (i8::MIN.into(), i8::MAX.into()),
+        "u16" => (0, u16::MAX.into()),
+        "i16" => (i16::MIN.into(), i16::MAX.into()),
+        "u32" => (0, u32::MAX.into()),
+        "i32" => (i32::MIN.into(), i32::MAX.into()),
+        "u64" | "usize" => (0, u64::MAX.into()),
+        "i64" | "isize" => (i64::MIN.into(), i64::MAX.into()),
+        _ => return None,
+    })
+}
@@ -0,0 +1,187 @@
+use darling::FromField;
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::quote;
+use syn::{Data, DataEnum, Field, Fields, Index};
+
+use crate::utils::unnamed_binding;
+use crate::{parse_path, variant_name_arm, FieldAttr};
+
+/// Generates `EguiInspect::inspect_diff`, diffing field by field (and for enums, variant fields
+/// when both values are of the same variant)
+pub(crate) fn inspect_diff_fn(data: &Data, name: &Ident) -> TokenStream {
+    let diff = match data {
+        Data::Struct(data) => {
+            let field_diffs = fields_diffs(&data.fields, false);
+            quote! {
+                #[allow(unused_mut)]
+                let mut differs = false;
+                #field_diffs
+                differs
+            }
+        }
+        Data::Enum(data_enum) => enum_diff(data_enum, name),
+        Data::Union(_) => unreachable!("unions are rejected by checks::check_input"),
+    };
+
+    quote! {
+        fn inspect_diff(&self, other: &Self, label: &str, ui: &mut egui_inspect::egui::Ui) -> bool {
+            egui_inspect::diff::diff_group(label, ui, |ui| {
+                #diff
+            })
+        }
+    }
+}
+
+fn enum_diff(data_enum: &DataEnum, name: &Ident) -> TokenStream {
+    let name_arms: Vec<_> = data_enum
+        .variants
+        .iter()
+        .map(|v| variant_name_arm(v, name))
+        .collect();
+
+    let arms = data_enum.variants.iter().map(|v| {
+        let ident = &v.ident;
+        let field_diffs = fields_diffs(&v.fields, true);
+        match &v.fields {
+            Fields::Named(fields) => {
+                let left = fields.named.iter().map(|f| &f.ident);
+                let right = fields.named.iter().map(|f| {
+                    let field = &f.ident;
+                    let binding = other_binding(&f.ident.as_ref().unwrap().to_string());
+                    quote!(#field: #binding)
+                });
+                quote!((#name::#ident { #(#left),* }, #name::#ident { #(#right),* }) => {
+                    #field_diffs
+                })
+            }
+            Fields::Unnamed(fields) => {
+                let left = (0..fields.unnamed.len()).map(unnamed_binding);
+                let right = (0..fields.unnamed.len())
+                    .map(|i| other_binding(&unnamed_binding(i).to_string()));
+                quote!((#name::#ident ( #(#left),* ), #name::#ident ( #(#right),* )) => {
+                    #field_diffs
+                })
+            }
+            Fields::Unit => quote!((#name::#ident, #name::#ident) => {}),
+        }
+    });
+
+    quote! {
+        let self_variant = match self {
+            #(#name_arms,)*
+        };
+        let other_variant = match other {
+            #(#name_arms,)*
+        };
+        #[allow(unused_mut)]
+        let mut differs = egui_inspect::diff::diff_leaf(&self_variant, &other_variant, "", ui);
+        #[allow(unused_variables, unreachable_patterns)]
+        match (self, other) {
+            #(#arms)*
+            _ => {
+                egui_inspect::diff::diff_columns(
+                    ui,
+                    egui_inspect::diff::DiffKind::Changed,
+                    |ui| egui_inspect::EguiInspect::inspect(self, "", ui),
+                    |ui| egui_inspect::EguiInspect::inspect(other, "", ui),
+                );
+            }
+        }
+        differs
+    }
+}
+
+fn other_binding(binding: &str) -> Ident {
+    Ident::new(format!("other_{binding}").as_str(), Span::call_site())
+}
+
+/// Diff statements of all fields, accumulating into `differs`. `loose` fields are bound by
+/// reference within enum variant arms (the right hand side ones prefixed by `other_`).
+fn fields_diffs(fields: &Fields, loose: bool) -> TokenStream {
+    match fields {
+        Fields::Named(fields) => fields
+            .named
+            .iter()
+            .map(|f| {
+                let name = f.ident.as_ref().unwrap();
+                let (left, right) = match loose {
+                    true => {
+                        let other = other_binding(&name.to_string());
+                        (quote!(#name), quote!(#other))
+                    }
+                    false => (quote!(&self.#name), quote!(&other.#name)),
+                };
+                named_field_diff(f, left, right)
+            })
+            .collect(),
+        Fields::Unnamed(fields) => fields
+            .unnamed
+            .iter()
+            .enumerate()
+            .map(|(i, f)| {
+                let attr = FieldAttr::from_field(f).expect("Could not get attributes from field");
+                // fields drawn by a custom function need not be EguiInspect, so they are skipped
+                if attr.hide || attr.custom_func.is_some() || attr.custom_func_mut.is_some() {
+                    return quote!();
+                }
+                let (left, right, default_label) = match loose {
+                    true => {
+                        let binding = unnamed_binding(i);
+                        let other = other_binding(&binding.to_string());
+                        (quote!(#binding), quote!(#other), String::new())
+                    }
+                    false => {
+                        let index = Index::from(i);
+                        (
+                            quote!(&self.#index),
+                            quote!(&other.#index),
+                            format!("Field {i}"),
+                        )
+                    }
+                };
+                let label = attr.name.unwrap_or(default_label);
+                quote! {
+                    differs |= ui.push_id(#i, |ui| {
+                        egui_inspect::EguiInspect::inspect_diff(#left, #right, #label, ui)
+                    }).inner;
+                }
+            })
+            .collect(),
+        Fields::Unit => quote!(),
+    }
+}
+
+fn named_field_diff(f: &Field, left: TokenStream, right: TokenStream) -> TokenStream {
+    let attr = FieldAttr::from_field(f).expect("Could not get attributes from field");
+    if attr.hide {
+        return quote!();
+    }
+
+    let id_salt = f.ident.as_ref().unwrap().to_string();
+    let name_str = attr.name.clone().unwrap_or_else(|| id_salt.clone());
+
+    // fields drawn by a custom function need not be EguiInspect, so they can't be compared
+    if let Some(custom_func) = &attr.custom_func {
+        let custom_func = parse_path(custom_func);
+        return quote! {
+            ui.push_id(#id_salt, |ui| {
+                egui_inspect::diff::diff_columns(
+                    ui,
+                    egui_inspect::diff::DiffKind::Same,
+                    |ui| {
+                        #custom_func(#left, &#name_str, ui);
+                    },
+                    |ui| {
+                        #custom_func(#right, &#name_str, ui);
+                    },
+                );
+            });
+        };
+    }
+
+    quote! {
+        differs |= ui.push_id(#id_salt, |ui| {
+            egui_inspect::EguiInspect::inspect_diff(#left, #right, &#name_str, ui)
+        }).inner;
+    }
+}
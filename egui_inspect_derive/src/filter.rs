@@ -0,0 +1,132 @@
+use darling::FromField;
+use proc_macro2::{Ident, TokenStream};
+use quote::quote;
+use syn::{Data, Field, Fields, FieldsNamed, Index};
+
+use crate::utils::unnamed_binding;
+use crate::{field_condition, field_name, FieldAttr};
+
+/// Generates `EguiInspect::matches_filter`, true if the label of a (visible) field, or of anything
+/// nested in one, matches the query. For enums, only the fields of the current variant count.
+pub(crate) fn matches_filter_fn(data: &Data, name: &Ident) -> TokenStream {
+    let matches = match data {
+        Data::Struct(data) => fields_matches(&data.fields, false),
+        Data::Enum(data_enum) => {
+            let arms = data_enum.variants.iter().map(|v| {
+                let ident = &v.ident;
+                let matches = fields_matches(&v.fields, true);
+                match &v.fields {
+                    Fields::Named(fields) => {
+                        let bindings = fields.named.iter().map(|f| &f.ident);
+                        quote!(#name::#ident { #(#bindings),* } => #matches)
+                    }
+                    Fields::Unnamed(fields) => {
+                        let bindings = (0..fields.unnamed.len()).map(unnamed_binding);
+                        quote!(#name::#ident ( #(#bindings),* ) => #matches)
+                    }
+                    Fields::Unit => quote!(#name::#ident => false),
+                }
+            });
+            quote! {
+                #[allow(unused_variables)]
+                match self {
+                    #(#arms),*
+                }
+            }
+        }
+        Data::Union(_) => unreachable!("unions are rejected by checks::check_input"),
+    };
+
+    quote! {
+        fn matches_filter(&self, query: &str) -> bool {
+            #matches
+        }
+    }
+}
+
+/// `||` of the matches of all fields, `loose` fields are bound by reference (within enum variant
+/// arms)
+fn fields_matches(fields: &Fields, loose: bool) -> TokenStream {
+    let matches: Vec<_> = match fields {
+        Fields::Named(fields) => fields
+            .named
+            .iter()
+            .filter_map(|f| named_field_matches(f, fields, loose))
+            .collect(),
+        Fields::Unnamed(fields) => fields
+            .unnamed
+            .iter()
+            .enumerate()
+            .filter_map(|(i, f)| {
+                let attr = FieldAttr::from_field(f).expect("Could not get attributes from field");
+                if attr.hide {
+                    return None;
+                }
+                let value = match loose {
+                    true => {
+                        let binding = unnamed_binding(i);
+                        quote!(#binding)
+                    }
+                    false => {
+                        let index = Index::from(i);
+                        quote!(&self.#index)
+                    }
+                };
+                // unnamed fields are only matched by label if given a name
+                let label_matches = attr
+                    .name
+                    .map(|name| quote!(egui_inspect::filter::label_matches(#name, query) ||));
+                Some(match attr.custom_func.is_some() || attr.custom_func_mut.is_some() {
+                    true => quote!((#label_matches false)),
+                    false => quote! {
+                        (#label_matches egui_inspect::EguiInspect::matches_filter(#value, query))
+                    },
+                })
+            })
+            .collect(),
+        Fields::Unit => vec![],
+    };
+    match matches.is_empty() {
+        true => quote!(false),
+        false => quote!(#(#matches)||*),
+    }
+}
+
+fn named_field_matches(f: &Field, siblings: &FieldsNamed, loose: bool) -> Option<TokenStream> {
+    let attr = FieldAttr::from_field(f).expect("Could not get attributes from field");
+    if attr.hide {
+        return None;
+    }
+
+    // flattened fields have no label to match
+    let name_str = match attr.flatten {
+        true => String::new(),
+        false => field_name(f, &attr),
+    };
+    let label_matches = quote!(egui_inspect::filter::label_matches(#name_str, query));
+    // fields drawn by a custom function need not be EguiInspect
+    let matches = if attr.custom_func.is_some() || attr.custom_func_mut.is_some() {
+        label_matches
+    } else {
+        let name = &f.ident;
+        let value = match loose {
+            true => quote!(#name),
+            false => quote!(&self.#name),
+        };
+        quote!((#label_matches || egui_inspect::EguiInspect::matches_filter(#value, query)))
+    };
+    // fields are shown in full when the name of their group matches
+    let matches = match &attr.group {
+        Some(group) => {
+            quote!((egui_inspect::filter::label_matches(#group, query) || #matches))
+        }
+        None => matches,
+    };
+    Some(match &attr.visible_if {
+        Some(visible_if) => {
+            let visible = field_condition(visible_if, siblings, loose);
+            quote!((#visible && #matches))
+        }
+        None => matches,
+    })
+}
@@ -0,0 +1,198 @@
+use darling::{FromField, FromMeta};
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use syn::{Field, FieldsNamed};
+
+use crate::{
+    collect_field_inspects, contains_match, field_condition, field_name, handle_grid_fields,
+    handle_named_field, variant_condition, FieldAttr,
+};
+
+/// How the groups of fields are drawn (see `egui_inspect::groups`)
+#[derive(Clone, Copy, Debug, Default, FromMeta)]
+#[darling(rename_all = "snake_case")]
+pub(crate) enum GroupStyle {
+    #[default]
+    Sections,
+    Collapsing,
+    Tabs,
+}
+
+/// A run of consecutive ungrouped fields, or all the fields of a group
+enum Block<'a> {
+    Fields(Vec<&'a Field>),
+    Group(String, Vec<&'a Field>),
+}
+
+/// Inspect block of named fields laid out in their groups, sorted by `order`. `None` if no field
+/// is grouped or ordered, as they are then drawn as declared.
+pub(crate) fn grouped_fields(
+    fields: &FieldsNamed,
+    style: GroupStyle,
+    mutable: bool,
+    loose: bool,
+    grid: bool,
+) -> Option<TokenStream> {
+    let mut attrs: Vec<_> = fields
+        .named
+        .iter()
+        .map(|f| {
+            let attr = FieldAttr::from_field(f).expect("Could not get attributes from field");
+            (f, attr)
+        })
+        .filter(|(_, attr)| !attr.hide)
+        .collect();
+    if attrs
+        .iter()
+        .all(|(_, attr)| attr.group.is_none() && attr.order.is_none())
+    {
+        return None;
+    }
+    // NOTE: the sort is stable, so ties keep their declaration order
+    attrs.sort_by_key(|(_, attr)| attr.order.unwrap_or(0));
+
+    let mut blocks: Vec<Block> = vec![];
+    for (f, attr) in &attrs {
+        match &attr.group {
+            Some(group) => {
+                let existing = blocks.iter_mut().find_map(|block| match block {
+                    Block::Group(name, fields) if name == group => Some(fields),
+                    _ => None,
+                });
+                match existing {
+                    Some(group_fields) => group_fields.push(f),
+                    None => blocks.push(Block::Group(group.clone(), vec![f])),
+                }
+            }
+            // NOTE: tabs only show one group at a time, so the ungrouped fields all go above them
+            None if matches!(style, GroupStyle::Tabs) => match blocks.first_mut() {
+                Some(Block::Fields(run)) => run.push(f),
+                _ => blocks.insert(0, Block::Fields(vec![f])),
+            },
+            None => match blocks.last_mut() {
+                Some(Block::Fields(run)) => run.push(f),
+                _ => blocks.push(Block::Fields(vec![f])),
+            },
+        }
+    }
+
+    let fields_block = |block_fields: &[&Field]| match grid {
+        true => handle_grid_fields(block_fields, fields, mutable),
+        false => {
+            let field_inspects = block_fields
+                .iter()
+                .filter_map(|f| handle_named_field(f, fields, mutable, loose))
+                .collect();
+            collect_field_inspects(field_inspects, mutable)
+        }
+    };
+
+    let mut statements = vec![];
+    let mut tabs_drawn = false;
+    for block in &blocks {
+        match (block, style) {
+            (Block::Fields(run), _) => {
+                let inspect = fields_block(run);
+                statements.push(match mutable {
+                    true => quote!(response |= #inspect;),
+                    false => quote!(#inspect;),
+                });
+            }
+            (Block::Group(..), GroupStyle::Tabs) => {
+                // NOTE: all groups share the tab bar placed at the first one
+                if tabs_drawn {
+                    continue;
+                }
+                tabs_drawn = true;
+                let groups: Vec<_> = blocks
+                    .iter()
+                    .filter_map(|block| match block {
+                        Block::Group(name, group_fields) => Some((name, group_fields)),
+                        Block::Fields(_) => None,
+                    })
+                    .collect();
+                let names = groups.iter().map(|(name, _)| name);
+                let matches = groups
+                    .iter()
+                    .map(|(name, group_fields)| group_match(name, group_fields, fields, loose));
+                let arms = groups.iter().enumerate().map(|(i, (name, group_fields))| {
+                    let inspect = fields_block(group_fields);
+                    let show = quote! {
+                        egui_inspect::filter::show_field(ui, #name, contains_match[#i], |ui| #inspect)
+                    };
+                    match mutable {
+                        true => quote! {
+                            #i => if let Some(group_response) = #show {
+                                response |= group_response;
+                            }
+                        },
+                        false => quote!(#i => { #show; }),
+                    }
+                });
+                statements.push(quote! {{
+                    let contains_match = [#(#matches),*];
+                    match egui_inspect::groups::tabs(ui, &[#(#names),*], &contains_match) {
+                        #(#arms,)*
+                        _ => {}
+                    }
+                }});
+            }
+            (Block::Group(name, group_fields), style) => {
+                let inspect = fields_block(group_fields);
+                let matches = group_match(name, group_fields, fields, loose);
+                statements.push(match (style, mutable) {
+                    (GroupStyle::Collapsing, true) => quote! {{
+                        let contains_match = #matches;
+                        if let Some(collapsing) = egui_inspect::groups::collapsing(ui, #name, contains_match, |ui| #inspect) {
+                            response |= egui_inspect::utils::collapsing_response(collapsing);
+                        }
+                    }},
+                    (GroupStyle::Collapsing, false) => quote! {{
+                        let contains_match = #matches;
+                        egui_inspect::groups::collapsing(ui, #name, contains_match, |ui| #inspect);
+                    }},
+                    (_, true) => quote! {{
+                        let contains_match = #matches;
+                        if let Some(group_response) = egui_inspect::groups::section(ui, #name, contains_match, |ui| #inspect) {
+                            response |= group_response;
+                        }
+                    }},
+                    (_, false) => quote! {{
+                        let contains_match = #matches;
+                        egui_inspect::groups::section(ui, #name, contains_match, |ui| #inspect);
+                    }},
+                });
+            }
+        }
+    }
+    Some(collect_field_inspects(statements, mutable))
+}
+
+/// Expression of whether the active field filter matches the group name, or one of its (visible)
+/// fields
+fn group_match(
+    name: &str,
+    group_fields: &[&Field],
+    siblings: &FieldsNamed,
+    loose: bool,
+) -> TokenStream {
+    let field_matches = group_fields.iter().map(|f| {
+        let attr = FieldAttr::from_field(f).expect("Could not get attributes from field");
+        let name_str = field_name(f, &attr);
+        let contains_match = contains_match(f, &attr, loose);
+        let matches = quote! {
+            (egui_inspect::filter::label_matches_active(ui, #name_str) || #contains_match)
+        };
+        match &attr.visible_if {
+            Some(visible_if) => {
+                let visible = match loose {
+                    true => variant_condition(f, "visible").into_token_stream(),
+                    false => field_condition(visible_if, siblings, false),
+                };
+                quote!((#visible && #matches))
+            }
+            None => matches,
+        }
+    });
+    quote!(egui_inspect::filter::label_matches_active(ui, #name) #(|| #field_matches)*)
+}
@@ -1,14 +1,20 @@
 use proc_macro2::{Ident, Span, TokenStream};
-use quote::{quote, quote_spanned};
+use quote::{quote, quote_spanned, ToTokens};
 use syn::spanned::Spanned;
 use syn::{
-    parse_macro_input, parse_quote, Data, DataEnum, DeriveInput, Field, Fields, FieldsNamed, FieldsUnnamed, GenericParam, Generics, Index, Variant
+    parse_macro_input, parse_quote, Data, DataEnum, DataStruct, DeriveInput, Field, Fields, FieldsNamed, FieldsUnnamed, GenericParam, Generics, Lit, Variant
 };
 
-use darling::{FromDeriveInput, FromField, FromMeta};
+use darling::{FromDeriveInput, FromField, FromMeta, FromVariant};
 
+mod checks;
+mod diff;
+mod filter;
+mod groups;
 mod internal_paths;
+mod typed_attrs;
 mod utils;
+mod validation;
 
 // TODO: post_inspect would be more useful as an EguiInspect attribute, or on both?
 #[derive(Clone, Debug, Default, FromField, FromDeriveInput)]
@@ -18,7 +24,8 @@ struct EframeMainAttr {
     title: Option<String>,
     /// How to generate `eframe::NativeOptions` (defaults to `NativeOptions::default()`)
     options: Option<String>,
-    /// How to initialise the Application object (defaults to `Self::Default()`)
+    /// How to initialise the Application object (defaults to `Self::Default()`), given the
+    /// `eframe::CreationContext` as `cc`
     init: Option<String>,
     /// Code to run after the `Self::egui_inspect` call within the generated `Eframe::App::update`
     post_inspect: Option<String>,
@@ -52,7 +59,7 @@ struct EframeMainAttr {
 ///}
 ///
 ///#[derive(Default, Debug, EguiInspect, EframeMain)]
-///#[eframe_main(post_inspect = "self.post_inspect(ui);")]
+///#[eframe_main(post_inspect = self.post_inspect(ui))]
 ///struct ReflectMe {
 ///    #[inspect(slider, min = 10.0, max = 20.0)]
 ///    input: u16,
@@ -85,7 +92,8 @@ struct EframeMainAttr {
 ///    title: Option<String>,
 ///    /// How to generate `eframe::NativeOptions` (defaults to `NativeOptions::default()`)
 ///    options: Option<String>,
-///    /// How to initialise the Application object (defaults to `Self::Default()`)
+///    /// How to initialise the Application object (defaults to `Self::Default()`), given the
+///    /// `eframe::CreationContext` as `cc`
 ///    init: Option<String>,
 ///    /// Code to run after the `Self::egui_inspect` call within the generated `Eframe::App::update`
 ///    post_inspect: Option<String>,
@@ -94,6 +102,27 @@ struct EframeMainAttr {
 ///}
 ///```
 ///
+/// `options`, `init` and `post_inspect` are Rust expressions, e.g. `init = MyApp::new()`, also
+/// accepted as strings (`init = "MyApp::new()"`). `init` may use the [eframe::CreationContext] as
+/// `cc`, e.g. to set up the fonts or get the rendering context:
+/// ```
+/// use egui_inspect::{eframe, EframeMain, EguiInspect};
+///
+/// #[derive(EguiInspect, EframeMain)]
+/// #[eframe_main(init = MyApp::new(cc))]
+/// struct MyApp {
+///     zoom: f32,
+/// }
+///
+/// impl MyApp {
+///     fn new(cc: &eframe::CreationContext) -> Self {
+///         Self {
+///             zoom: cc.egui_ctx.zoom_factor(),
+///         }
+///     }
+/// }
+/// ```
+///
 /// When compiling for WASM, one may need to append the following to their Cargo.toml:
 ///
 /// ```toml
@@ -111,14 +140,30 @@ struct EframeMainAttr {
 /// see https://github.com/emilk/eframe_template for more on this.
 #[proc_macro_derive(EframeMain, attributes(eframe_main))]
 pub fn derive_eframe_main(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
-    let input = parse_macro_input!(input as DeriveInput);
+    let mut input = parse_macro_input!(input as DeriveInput);
+    let _typed_values = typed_attrs::stringify_typed_values(&mut input, "eframe_main");
     let ident = input.ident.clone();
-    let attr = EframeMainAttr::from_derive_input(&input).unwrap();
+    let attr = match EframeMainAttr::from_derive_input(&input) {
+        Ok(attr) => attr,
+        Err(e) => return e.write_errors().into(),
+    };
     let title = attr.title.unwrap_or(ident.to_string());
-    // TODO: Accept expressions/tokens rather than strings
-    let options: TokenStream = attr.options.unwrap_or("Default::default()".to_string()).parse().unwrap();
-    let init: TokenStream = attr.init.unwrap_or(format!("{ident}::default()")).parse().unwrap();
-    let post_inspect: TokenStream = attr.post_inspect.unwrap_or(String::new()).parse().unwrap();
+    let mut errors = darling::Error::accumulator();
+    let mut parse_option = |option: &str, value: String| {
+        let parsed = typed_attrs::code_tokens(&value).map_err(|e| {
+            darling::Error::custom(format!("`{option}` is not valid Rust ({e}): {value:?}"))
+                .with_span(&ident)
+        });
+        errors.handle(parsed).unwrap_or_default()
+    };
+    let options = parse_option("options", attr.options.unwrap_or("Default::default()".to_string()));
+    let init = parse_option("init", attr.init.unwrap_or(format!("{ident}::default()")));
+    let post_inspect = parse_option("post_inspect", attr.post_inspect.unwrap_or_default());
+    if let Err(e) = errors.finish() {
+        return e.write_errors().into();
+    }
+    // NOTE: `init` may use the creation context as `cc`
+    let creator = quote!(Box::new(|cc| Ok(Box::new(#init))));
     let eframe_app_derive = match attr.no_eframe_app_derive {
         true => quote!{
         },
@@ -144,7 +189,7 @@ pub fn derive_eframe_main(input: proc_macro::TokenStream) -> proc_macro::TokenSt
             egui_inspect::eframe::run_native(
                 #title,
                 #options,
-                Box::new(|_cc| Ok(Box::new(#init))),
+                #creator,
             )
         }
 
@@ -167,7 +212,7 @@ pub fn derive_eframe_main(input: proc_macro::TokenStream) -> proc_macro::TokenSt
                     .start(
                         canvas,
                         #options,
-                        Box::new(|_cc| Ok(Box::new(#init))),
+                        #creator,
                     )
                     .await;
 
@@ -192,8 +237,8 @@ pub fn derive_eframe_main(input: proc_macro::TokenStream) -> proc_macro::TokenSt
     .into()
 }
 
-/// Derives a impl for PartialEq that only considers an enums discriminant (variants) for
-/// EguiInspect derivation (dropdowns) for enums.
+/// Derives a impl for PartialEq that only considers an enums discriminant (variants). No longer
+/// needed by the EguiInspect derive, whose variant selectors compare discriminants themselves.
 #[proc_macro_derive(DPEQ, attributes(__dpeq__))]
 pub fn derive_dpeq(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
@@ -223,17 +268,137 @@ struct FieldAttr {
     /// Use logarithmic slider function for numbers
     log_slider: bool,
     /// Min value for numbers
-    min: Option<f32>,
+    min: Option<Number>,
     /// Max value for numbers
-    max: Option<f32>,
+    max: Option<Number>,
+    /// Numbers snap to multiples of this step (from `min`)
+    step: Option<Number>,
+    /// Drag speed of numbers, per point
+    speed: Option<Number>,
+    /// Text shown before numbers
+    prefix: Option<String>,
+    /// Text shown after numbers, e.g. their unit
+    suffix: Option<String>,
+    /// Number of decimals shown for numbers
+    decimals: Option<usize>,
+    /// How numbers are kept within `min..=max`
+    clamp: Option<Clamping>,
+    /// How numbers are displayed
+    format: Option<NumberFormat>,
     /// Display mut text on multiple line
     multiline: bool,
+    /// Mask the text of strings
+    password: bool,
+    /// Placeholder shown while a string is empty
+    hint: Option<String>,
+    /// Max number of characters of strings
+    max_len: Option<usize>,
+    /// Characters allowed in strings
+    charset: Option<Charset>,
+    /// Pattern strings should match, flagged while they do not
+    regex: Option<String>,
+    /// Edit strings as source code in this language
+    code: Option<String>,
+    /// Render strings as Markdown
+    markdown: bool,
+    /// Offer to duplicate the elements of sequences
+    duplicate: bool,
     /// Use custom function for non-mut inspect
     custom_func: Option<String>,
     /// Use custom function for mut inspect
     custom_func_mut: Option<String>,
     /// Use a button for a bool field (will only be set to true during the frame that it is pressed)
     button: bool,
+    /// Path of a `fn(&T) -> Result<(), String>`, errors are displayed under the field
+    validate: Option<String>,
+    /// Expression deciding whether the field is shown, sibling fields are in scope by reference,
+    /// as is `self`
+    visible_if: Option<String>,
+    /// Expression deciding whether the field is editable (greyed out otherwise), sibling fields
+    /// are in scope by reference, as is `self`
+    enabled_if: Option<String>,
+    /// Name of the group the field is drawn in, along with the other fields of that group
+    group: Option<String>,
+    /// Position of the field (and of its group) relative to the others, `0` by default
+    order: Option<i32>,
+    /// Draw the fields of the (derived) value inline with the parent's, without frame or label
+    flatten: bool,
+}
+
+/// Number literal attribute, either integer or float (e.g. `min = 0` or `max = 2.5`). Integers
+/// are kept exactly, as `f64` cannot represent all of `u64` or `i64`.
+#[derive(Clone, Copy, Debug)]
+enum Number {
+    Int(i128),
+    Float(f64),
+}
+
+impl Number {
+    fn as_f64(self) -> f64 {
+        match self {
+            Number::Int(i) => i as f64,
+            Number::Float(f) => f,
+        }
+    }
+
+    fn greater_than(self, other: Number) -> bool {
+        match (self, other) {
+            (Number::Int(a), Number::Int(b)) => a > b,
+            _ => self.as_f64() > other.as_f64(),
+        }
+    }
+
+    /// Runtime `egui_inspect::options::NumberBound` of the number
+    fn bound(self) -> TokenStream {
+        match self {
+            Number::Int(i) => quote!(egui_inspect::options::NumberBound::Int(#i)),
+            Number::Float(f) => quote!(egui_inspect::options::NumberBound::Float(#f)),
+        }
+    }
+}
+
+impl FromMeta for Number {
+    fn from_value(value: &Lit) -> darling::Result<Self> {
+        match value {
+            Lit::Int(i) => i.base10_parse().map(Number::Int),
+            Lit::Float(f) => f.base10_parse().map(Number::Float),
+            _ => return Err(darling::Error::unexpected_lit_type(value)),
+        }
+        .map_err(|e| darling::Error::custom(e).with_span(value))
+    }
+}
+
+/// How numbers are kept within their bounds (see `egui::SliderClamping`)
+#[derive(Clone, Copy, Debug, FromMeta)]
+#[darling(rename_all = "snake_case")]
+enum Clamping {
+    Always,
+    Edits,
+    Never,
+}
+
+/// Characters allowed in strings (see `egui_inspect::options::Charset`)
+#[derive(Clone, Copy, Debug, FromMeta)]
+#[darling(rename_all = "snake_case")]
+enum Charset {
+    Ascii,
+    Alphanumeric,
+    Digits,
+    Hex,
+    Identifier,
+    FileName,
+}
+
+/// Display format of numbers (see `egui_inspect::options::NumberFormat`)
+#[derive(Clone, Copy, Debug, PartialEq, FromMeta)]
+#[darling(rename_all = "snake_case")]
+enum NumberFormat {
+    Plain,
+    Hex,
+    Binary,
+    Scientific,
+    Percent,
+    Thousands,
 }
 
 #[derive(Clone, Debug, Default, FromDeriveInput)]
@@ -249,6 +414,21 @@ struct DeriveAttr {
     /// If a parameter is only used in hidden fields, it does not need to be EguiInspect.
     no_trait_bound: Option<String>,
     // TODO: Multiple exempt parameters, ideally automatically detected.
+    /// Path of a `fn(&Self) -> Result<(), String>`, errors are displayed under the struct
+    validate: Option<String>,
+    /// Lay out named fields in (label | value) grid columns, defaults to
+    /// `egui_inspect::grid::set_grid_by_default`
+    grid: Option<bool>,
+    /// How groups of fields are drawn: `"sections"` (default), `"collapsing"` or `"tabs"`
+    groups: groups::GroupStyle,
+    /// Select enum variants with radio buttons rather than a combo box
+    radio: bool,
+    /// Select enum variants with a tab bar rather than a combo box
+    tabs: bool,
+    /// Select enum variants with a row of joined buttons rather than a combo box
+    segmented: bool,
+    /// Draw a struct with a single field exactly as that field (e.g. for newtypes)
+    transparent: bool,
 }
 
 // TODO: keep structs in sync after changes, or just reference them by tag and use jump to
@@ -289,6 +469,21 @@ struct DeriveAttr {
 ///    on_hover_text: Option<String>,
 ///    /// If a parameter is only used in hidden fields, it does not need to be EguiInspect.
 ///    no_trait_bound: Option<String>,
+///    /// Path of a `fn(&Self) -> Result<(), String>`, errors are displayed under the struct
+///    validate: Option<String>,
+///    /// Lay out named fields in (label | value) grid columns, defaults to
+///    /// `egui_inspect::grid::set_grid_by_default`
+///    grid: Option<bool>,
+///    /// How groups of fields are drawn: `"sections"` (default), `"collapsing"` or `"tabs"`
+///    groups: String,
+///    /// Select enum variants with radio buttons rather than a combo box
+///    radio: bool,
+///    /// Select enum variants with a tab bar rather than a combo box
+///    tabs: bool,
+///    /// Select enum variants with a row of joined buttons rather than a combo box
+///    segmented: bool,
+///    /// Draw a struct with a single field exactly as that field (e.g. for newtypes)
+///    transparent: bool,
 ///}
 /// ```
 ///
@@ -306,21 +501,112 @@ struct DeriveAttr {
 ///    /// Use logarithmic slider function for numbers
 ///    log_slider: bool,
 ///    /// Min value for numbers
-///    min: Option<f32>,
+///    min: Option<f64>,
 ///    /// Max value for numbers
-///    max: Option<f32>,
+///    max: Option<f64>,
+///    /// Numbers snap to multiples of this step (from `min`)
+///    step: Option<f64>,
+///    /// Drag speed of numbers, per point
+///    speed: Option<f64>,
+///    /// Text shown before numbers
+///    prefix: Option<String>,
+///    /// Text shown after numbers, e.g. their unit
+///    suffix: Option<String>,
+///    /// Number of decimals shown for numbers
+///    decimals: Option<usize>,
+///    /// How numbers are kept within `min..=max`: `"always"` (default), `"edits"` or `"never"`
+///    clamp: Option<String>,
+///    /// How numbers are displayed: `"plain"` (default), `"hex"`, `"binary"`, `"scientific"`,
+///    /// `"percent"` or `"thousands"`
+///    format: Option<String>,
 ///    /// Display mut text on multiple line
 ///    multiline: bool,
+///    /// Mask the text of strings
+///    password: bool,
+///    /// Placeholder shown while a string is empty
+///    hint: Option<String>,
+///    /// Max number of characters of strings
+///    max_len: Option<usize>,
+///    /// Characters allowed in strings: `"ascii"`, `"alphanumeric"`, `"digits"`, `"hex"`,
+///    /// `"identifier"` or `"file_name"`
+///    charset: Option<String>,
+///    /// Pattern strings should match (see `egui_inspect::pattern`), flagged while they do not
+///    regex: Option<String>,
+///    /// Edit strings as source code in this language, e.g. `"glsl"`, `"toml"` or `"rust"` (see
+///    /// `egui_inspect::code_editor`)
+///    code: Option<String>,
+///    /// Render strings as Markdown, edited next to their rendering (see `egui_inspect::markdown`)
+///    markdown: bool,
+///    /// Offer to duplicate the elements of `Vec` and `VecDeque` fields, whose elements must be
+///    /// `Clone` (see `egui_inspect::vec_editor`)
+///    duplicate: bool,
 ///    /// Use custom function for non-mut inspect
 ///    custom_func: Option<String>,
 ///    /// Use custom function for mut inspect
 ///    custom_func_mut: Option<String>,
+///    /// Use a button for a bool field (will only be set to true during the frame that it is pressed)
+///    button: bool,
+///    /// Path of a `fn(&T) -> Result<(), String>`, errors are displayed under the field
+///    validate: Option<String>,
+///    /// Expression deciding whether the field is shown, sibling fields are in scope by reference,
+///    /// as is `self`
+///    visible_if: Option<String>,
+///    /// Expression deciding whether the field is editable (greyed out otherwise), sibling fields
+///    /// are in scope by reference, as is `self`
+///    enabled_if: Option<String>,
+///    /// Name of the group the field is drawn in, along with the other fields of that group
+///    group: Option<String>,
+///    /// Position of the field (and of its group) relative to the others, `0` by default
+///    order: Option<i32>,
+///    /// Draw the fields of the (derived) value inline with the parent's, without frame or label
+///    flatten: bool,
 ///}
 /// ```
+///
+/// And enum variants by the fields of:
+/// ```
+///struct VariantAttr {
+///    /// Name of the variant to be displayed in the selector
+///    name: Option<String>,
+///    /// Leaves the variant out of the selector
+///    hide: bool,
+///    /// Hover text of the variant in the selector (defaults to its doc comment)
+///    doc: Option<String>,
+///    /// Expression of the value the variant is selected with (defaults to its fields defaults)
+///    ctor: Option<String>,
+///}
+/// ```
+///
+/// When another variant is selected, the named fields it shares with the previous one are carried
+/// over. Fields are shared when they have the same name and the same type *as written*, token for
+/// token: `Vec<u8>` and `std::vec::Vec<u8>`, or a type and its alias, are different types here.
+///
+/// Besides `inspect` and `inspect_mut`, `inspect_diff`, `is_inline` and `matches_filter` are
+/// generated, as is `validation_errors` when any field (or the struct) is annotated with
+/// `validate`, or may contain such fields.
+///
+/// The options holding code (`custom_func`, `custom_func_mut`, `validate`, `frame_style`,
+/// `visible_if`, `enabled_if` and `ctor`) take a Rust path or expression, e.g.
+/// `custom_func_mut = my_func` or `visible_if = *enabled`, which keeps its spans for errors and
+/// IDE navigation. The string forms (`custom_func_mut = "my_func"`) are still accepted.
+///
+/// Invalid attributes are reported as compile errors pointing at the offending option (all of
+/// them at once): unknown options, unparsable paths, expressions or `regex` patterns, options
+/// that do not apply to the field type (e.g. `slider` on a `String`) or that conflict with each
+/// other (e.g. `slider` and `log_slider`), and unsupported items (unions). See
+/// `egui_inspect/tests/ui` for examples.
 #[proc_macro_derive(EguiInspect, attributes(inspect))]
 pub fn derive_egui_inspect(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
-    let input = parse_macro_input!(input as DeriveInput);
-    let attr = DeriveAttr::from_derive_input(&input).unwrap();
+    let mut input = parse_macro_input!(input as DeriveInput);
+    let _typed_values = typed_attrs::stringify_typed_values(&mut input, "inspect");
+    let attr = match DeriveAttr::from_derive_input(&input) {
+        Ok(attr) => attr,
+        Err(e) => return e.write_errors().into(),
+    };
+    // NOTE: the generation below relies on these checks, and panics on anything they reject
+    if let Err(e) = checks::check_input(&input, &attr) {
+        return e.write_errors().into();
+    }
 
     let name = input.ident;
 
@@ -331,17 +617,64 @@ pub fn derive_egui_inspect(input: proc_macro::TokenStream) -> proc_macro::TokenS
     let generics = add_trait_bounds(input.generics, ignore_list);
     let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
-    let inspect = inspect_data(&input.data, &name, false, &attr);
-    let inspect_mut = inspect_data(&input.data, &name, true, &attr);
+    let doc = utils::doc_comment(&input.attrs);
+    let (inspect, inspect_mut) = match attr.transparent {
+        true => (
+            transparent_inspect(&input.data, false),
+            transparent_inspect(&input.data, true),
+        ),
+        false => (
+            inspect_data(&input.data, &name, false, &attr, &doc),
+            inspect_data(&input.data, &name, true, &attr, &doc),
+        ),
+    };
+    // NOTE: transparent structs are drawn flat already
+    let inspect_flat_fns = match attr.transparent {
+        true => quote!(),
+        false => {
+            let flat = inspect_flat(&input.data, &name, false, &attr);
+            let flat_mut = inspect_flat(&input.data, &name, true, &attr);
+            quote! {
+                fn inspect_flat(&self, ui: &mut egui_inspect::egui::Ui) {
+                    #flat
+                }
+                fn inspect_flat_mut(&mut self, ui: &mut egui_inspect::egui::Ui) -> egui_inspect::egui::Response {
+                    #flat_mut
+                }
+            }
+        }
+    };
+    let validation_errors = validation::validation_errors_fn(&input.data, &name, &attr);
+    let inspect_diff = diff::inspect_diff_fn(&input.data, &name);
+    let matches_filter = filter::matches_filter_fn(&input.data, &name);
+    // only fieldless enums (drawn as a selector) fit on a single line
+    let is_inline = match &input.data {
+        _ if attr.transparent => transparent_is_inline(&input.data),
+        Data::Enum(data_enum) => {
+            let inline = data_enum
+                .variants
+                .iter()
+                .all(|v| matches!(v.fields, Fields::Unit));
+            quote!(#inline)
+        }
+        _ => quote!(false),
+    };
 
     quote! {
         impl #impl_generics egui_inspect::EguiInspect for #name #ty_generics #where_clause {
             fn inspect(&self, label: &str, ui: &mut egui_inspect::egui::Ui) {
                 #inspect
             }
-            fn inspect_mut(&mut self, label: &str, ui: &mut egui_inspect::egui::Ui) {
+            fn inspect_mut(&mut self, label: &str, ui: &mut egui_inspect::egui::Ui) -> egui_inspect::egui::Response {
                 #inspect_mut
             }
+            #inspect_flat_fns
+            fn is_inline(&self) -> bool {
+                #is_inline
+            }
+            #matches_filter
+            #validation_errors
+            #inspect_diff
         }
     }
     .into()
@@ -360,65 +693,156 @@ fn add_trait_bounds(mut generics: Generics, ignore_list: Vec<&str>) -> Generics
     generics
 }
 
-fn inspect_data(data: &Data, _struct_name: &Ident, mutable: bool, attr: &DeriveAttr) -> TokenStream {
-    let mut inner = match data {
-        Data::Struct(data) => handle_fields(&data.fields, mutable),
-        Data::Enum(data_enum) => handle_enum(data_enum, _struct_name, mutable),
-        Data::Union(_) => unimplemented!("Unions are not yet supported"),
+/// Inspect of the fields (or for enums, of the variant selector and fields), without the frame,
+/// header or label drawn around them
+fn inspect_fields(
+    data: &Data,
+    _struct_name: &Ident,
+    mutable: bool,
+    attr: &DeriveAttr,
+) -> TokenStream {
+    match data {
+        Data::Struct(DataStruct {
+            fields: Fields::Named(fields),
+            ..
+        }) if attr.grid != Some(false) && !(attr.grid.is_none() && attr.horiz) => {
+            let grid = groups::grouped_fields(fields, attr.groups, mutable, false, true)
+                .unwrap_or_else(|| {
+                    let grid_fields: Vec<_> = fields.named.iter().collect();
+                    handle_grid_fields(&grid_fields, fields, mutable)
+                });
+            match attr.grid {
+                Some(_) => grid,
+                // NOTE: the default is set at runtime, see egui_inspect::grid::set_grid_by_default
+                None => {
+                    let fields = handle_fields(&Fields::Named(fields.clone()), mutable, attr.groups);
+                    quote! {
+                        if egui_inspect::grid::grid_by_default(ui) #grid else #fields
+                    }
+                }
+            }
+        }
+        Data::Struct(data) => handle_fields(&data.fields, mutable, attr.groups),
+        Data::Enum(data_enum) => handle_enum(data_enum, _struct_name, mutable, attr),
+        Data::Union(_) => unreachable!("unions are rejected by checks::check_input"),
+    }
+}
+
+/// `inspect_flat(_mut)`, the fields as drawn by [inspect_data] without the frame, header or label
+fn inspect_flat(data: &Data, struct_name: &Ident, mutable: bool, attr: &DeriveAttr) -> TokenStream {
+    let mut inner = with_struct_validation(inspect_fields(data, struct_name, mutable, attr), attr);
+    if attr.horiz {
+        inner = quote! {
+            ui.horizontal(|ui| #inner).inner
+        }
+    }
+    // NOTE: enum selectors are keyed by the label
+    quote!({
+        #[allow(unused_variables)]
+        let label = "";
+        #inner
+    })
+}
+
+/// Shows the error of the struct's `validate` function (if any) under the `inner` inspect
+fn with_struct_validation(inner: TokenStream, attr: &DeriveAttr) -> TokenStream {
+    let Some(validate) = &attr.validate else {
+        return inner;
     };
+    let validate = parse_path(validate);
+    quote!({
+        let scope = ui.scope(|ui| #inner);
+        if let Err(message) = #validate(self) {
+            egui_inspect::validation::show_validation_error(ui, scope.response.rect, &message);
+        }
+        scope.inner
+    })
+}
+
+fn inspect_data(
+    data: &Data,
+    _struct_name: &Ident,
+    mutable: bool,
+    attr: &DeriveAttr,
+    doc: &Option<String>,
+) -> TokenStream {
+    let mut inner = inspect_fields(data, _struct_name, mutable, attr);
 
     inner = if attr.collapsible {
-        quote!(
-            ui.collapsing(label, |ui| {
-                #inner
-            });
-        )
+        // NOTE: opened while a field filter matches something within
+        let header = quote! {
+            egui_inspect::egui::CollapsingHeader::new(
+                egui_inspect::filter::highlighted_label(ui, label, "")
+            )
+            .id_salt(label)
+            .open(egui_inspect::filter::force_open(ui, &*self))
+        };
+        // NOTE: the doc is taken before drawing the fields, which would otherwise claim it
+        let collapsing = quote! {
+            let doc = egui_inspect::help::take_doc(ui);
+            let mut collapsing = #header.show(ui, |ui| #inner);
+            collapsing.header_response = egui_inspect::help::show_doc(collapsing.header_response, doc);
+        };
+        if mutable {
+            quote!({
+                #collapsing
+                egui_inspect::utils::collapsing_response(collapsing)
+            })
+        } else {
+            quote!({
+                #collapsing
+            })
+        }
     } else {
-        quote!(
+        quote!({
             if label!="" {
-                ui.strong(label);
+                let header = ui.label(egui_inspect::filter::highlighted_label(ui, label, "").strong());
+                egui_inspect::help::attach_doc(ui, header);
             }
             #inner
-        )
+        })
     };
 
+    if let Some(doc) = doc {
+        inner = quote!(egui_inspect::help::with_type_doc(ui, #doc, |ui| #inner));
+    }
+
+    inner = with_struct_validation(inner, attr);
+
     if !attr.no_border {
-        let style_path_str = attr
-            .frame_style
-            .clone()
-            .unwrap_or("egui_inspect::DEFAULT_FRAME_STYLE".to_string());
-        let style_path: TokenStream = style_path_str.parse().unwrap();
+        let style_path = parse_path(
+            attr.frame_style
+                .as_deref()
+                .unwrap_or("egui_inspect::DEFAULT_FRAME_STYLE"),
+        );
         inner = quote! {
             #style_path
              .to_frame()
-             .show(ui, |ui| {
-                #inner
-            });
+             .show(ui, |ui| #inner)
+             .inner
         }
     };
 
     if attr.horiz {
         inner = quote! {
-            ui.horizontal(|ui|{
-                #inner
-            });
+            ui.horizontal(|ui| #inner).inner
         }
     }
 
     // TODO: Avoid double frame? (with border)
     if let Some(on_hover_text) = attr.on_hover_text.clone() {
-        inner = quote!(
-            egui_inspect::egui::Frame::none()
-                .show(ui, |ui| {
-                   #inner
-                }).response.on_hover_text_at_pointer(#on_hover_text);
-        );
+        inner = quote!({
+            let frame = egui_inspect::egui::Frame::none()
+                .show(ui, |ui| #inner);
+            frame.response.on_hover_text_at_pointer(#on_hover_text);
+            frame.inner
+        });
     }
 
     inner
 }
 
-fn handle_enum(data_enum: &DataEnum, struct_name: &Ident, mutable: bool) -> TokenStream {
+fn handle_enum(data_enum: &DataEnum, struct_name: &Ident, mutable: bool, attr: &DeriveAttr) -> TokenStream {
     let variants: Vec<_> = data_enum.variants.iter().collect();
     let name_arms = variants.iter().map(|v| variant_name_arm(v, struct_name));
 
@@ -428,184 +852,775 @@ fn handle_enum(data_enum: &DataEnum, struct_name: &Ident, mutable: bool) -> Toke
         };
     );
 
-    let combo_opts = variants.iter().map(|v| variant_combo(v, struct_name));
+    // NOTE: hidden variants are not offered, but still named while selected
+    let options = variants
+        .iter()
+        .filter(|v| !VariantAttr::from_variant(v).expect("Could not get attributes from variant").hide)
+        .map(|v| variant_option(v, &variants, struct_name, attr));
     let inspect_arms = variants
         .iter()
-        .map(|v| variant_inspect_arm(v, struct_name, mutable));
+        .map(|v| variant_inspect_arm(v, struct_name, mutable, attr.groups));
 
-    let variant_or_option = if mutable {
-        quote! {
-            ui.horizontal(|ui| {
-                egui_inspect::egui::ComboBox::new(format!("{self:p}").as_str(), "")
+    if mutable {
+        let selector = if attr.radio || attr.tabs || attr.segmented {
+            let layout = match attr.segmented {
+                true => quote!(ui.horizontal(|ui| {
+                    ui.spacing_mut().item_spacing.x = 0.0;
+                    let mut changed = false;
+                    #(changed |= #options.changed();)*
+                    changed
+                })),
+                false => quote!(ui.horizontal_wrapped(|ui| {
+                    let mut changed = false;
+                    #(changed |= #options.changed();)*
+                    changed
+                })),
+            };
+            let separator = match attr.tabs {
+                true => quote!(ui.separator();),
+                false => quote!(),
+            };
+            quote! {
+                let selector = #layout;
+                #separator
+                let mut response = egui_inspect::help::attach_doc(ui, selector.response);
+                if selector.inner {
+                    response.mark_changed();
+                }
+                response
+            }
+        } else {
+            quote!(ui.horizontal(|ui| {
+                let combo = egui_inspect::egui::ComboBox::from_id_salt(label)
                     .selected_text(current_variant)
                     .show_ui(ui, |ui| {
-                        #(#combo_opts;)*
+                        let mut changed = false;
+                        #(changed |= #options.changed();)*
+                        changed
                     });
-            });
-        }
+                // NOTE: the popup is on another layer, so only its changed flag is carried over
+                let mut response = egui_inspect::help::attach_doc(ui, combo.response);
+                if combo.inner == Some(true) {
+                    response.mark_changed();
+                }
+                response
+            }).inner)
+        };
+        quote!({
+            #reflect_variant_name
+
+            #[allow(unused_mut)]
+            let mut response = { #selector };
+
+            match self {
+                #(#inspect_arms),*
+            };
+
+            response
+        })
     } else {
-        quote! {
-            ui.label(current_variant);
-        }
-    };
+        quote!({
+            #reflect_variant_name
 
-    quote!(
-        #reflect_variant_name
+            let current = ui.label(current_variant);
+            egui_inspect::help::attach_doc(ui, current);
 
-        #variant_or_option
+            match self {
+                #(#inspect_arms),*
+            };
+        })
+    }
+}
 
-        match self {
-            #(#inspect_arms),*
-        };
-    )
+#[derive(Debug, FromVariant, Default)]
+#[darling(attributes(inspect), default)]
+struct VariantAttr {
+    /// Name of the variant to be displayed in the selector
+    name: Option<String>,
+    /// Leaves the variant out of the selector
+    hide: bool,
+    /// Hover text of the variant in the selector (defaults to its doc comment)
+    doc: Option<String>,
+    /// Expression of the value the variant is selected with (defaults to its fields defaults)
+    ctor: Option<String>,
+}
+
+/// Label of a variant
+fn variant_label(variant: &Variant) -> String {
+    VariantAttr::from_variant(variant)
+        .expect("Could not get attributes from variant")
+        .name
+        .unwrap_or_else(|| variant.ident.to_string())
 }
 
 fn variant_name_arm(variant: &Variant, struct_name: &Ident) -> TokenStream {
     let ident = &variant.ident;
+    let label = variant_label(variant);
     match &variant.fields {
         Fields::Named(_) => {
-            quote!(#struct_name::#ident {..} => stringify!(#ident))
+            quote!(#struct_name::#ident {..} => #label)
         }
         Fields::Unnamed(_) => {
-            quote!(#struct_name::#ident (..) => stringify!(#ident))
+            quote!(#struct_name::#ident (..) => #label)
         }
         Fields::Unit => {
-            quote!(#struct_name::#ident => stringify!(#ident))
+            quote!(#struct_name::#ident => #label)
         }
     }
 }
 
-fn variant_combo(variant: &Variant, struct_name: &Ident) -> TokenStream {
+/// Selector entry of a variant, as drawn for the enum's selector style. Selecting it replaces the
+/// value by the variant's `ctor` (or by its fields defaults), keeping the fields it shares (by name
+/// and type) with the previous variant.
+fn variant_option(
+    variant: &Variant,
+    variants: &[&Variant],
+    struct_name: &Ident,
+    attr: &DeriveAttr,
+) -> TokenStream {
     let ident = &variant.ident;
-    // TODO: Replace with handle_fields,
-    // which would need to take this ident as the base for fields instead of "self".
-    match &variant.fields {
-        Fields::Named(fields) => {
-            let defaults = fields.named.iter().map(|f| {
-                let ident = &f.ident;
-                quote!( #ident: Default::default() )
-            });
-            quote!(ui.selectable_value(self, 
-                                       #struct_name::#ident { #(#defaults),* }, 
-                                       stringify!(#ident)))
-        }
-        Fields::Unnamed(fields) => {
-            let defaults = fields.unnamed.iter().map(|_| quote!(Default::default()));
-            quote!(ui.selectable_value(self, #struct_name::#ident ( #(#defaults),* ), stringify!(#ident)))
+    let variant_attr = VariantAttr::from_variant(variant).expect("Could not get attributes from variant");
+    let label = variant_label(variant);
+    let value = match &variant_attr.ctor {
+        Some(ctor) => {
+            let ctor = parse_expr(ctor);
+            quote!(#ctor)
         }
-        Fields::Unit => {
-            quote!(ui.selectable_value(self, #struct_name::#ident, stringify!(#ident)))
+        None => match &variant.fields {
+            Fields::Named(fields) => {
+                let defaults = fields.named.iter().map(|f| {
+                    let ident = &f.ident;
+                    quote!( #ident: Default::default() )
+                });
+                quote!(#struct_name::#ident { #(#defaults),* })
+            }
+            Fields::Unnamed(fields) => {
+                let defaults = fields.unnamed.iter().map(|_| quote!(Default::default()));
+                quote!(#struct_name::#ident ( #(#defaults),* ))
+            }
+            Fields::Unit => {
+                quote!(#struct_name::#ident)
+            }
+        },
+    };
+    let pattern = match &variant.fields {
+        Fields::Named(_) => quote!(#struct_name::#ident { .. }),
+        Fields::Unnamed(_) => quote!(#struct_name::#ident ( .. )),
+        Fields::Unit => quote!(#struct_name::#ident),
+    };
+
+    let carry_arms: Vec<_> = variants
+        .iter()
+        .filter(|other| other.ident != variant.ident)
+        .filter_map(|other| carry_arm(other, variant, struct_name))
+        .collect();
+    let switch = match carry_arms.is_empty() {
+        true => quote!(*self = #value;),
+        false => quote! {
+            let previous = std::mem::replace(self, #value);
+            #[allow(unreachable_patterns)]
+            match (previous, &mut *self) {
+                #(#carry_arms)*
+                _ => {}
+            }
+        },
+    };
+
+    let widget = if attr.radio {
+        quote!(ui.radio(selected, #label))
+    } else if attr.segmented {
+        quote!(ui.add(egui_inspect::egui::Button::selectable(selected, #label).corner_radius(0)))
+    } else {
+        quote!(ui.selectable_label(selected, #label))
+    };
+    let widget = match variant_attr.doc.or_else(|| utils::doc_comment(&variant.attrs)) {
+        Some(doc) => quote!(#widget.on_hover_text(#doc)),
+        None => widget,
+    };
+    quote!({
+        let selected = matches!(self, #pattern);
+        let mut response = #widget;
+        if response.clicked() && !selected {
+            #switch
+            response.mark_changed();
         }
+        response
+    })
+}
+
+/// Match arm moving the named fields `from` shares (by name and type) with `to`, from the previous
+/// value into the new one (`None` if they share none). Types are compared as written, since they
+/// are not resolved yet: differently written forms of a type are not shared, rather than risking
+/// a type error on a move between different types of the same name.
+fn carry_arm(from: &Variant, to: &Variant, struct_name: &Ident) -> Option<TokenStream> {
+    let (Fields::Named(from_fields), Fields::Named(to_fields)) = (&from.fields, &to.fields) else {
+        return None;
+    };
+    let shared: Vec<_> = to_fields
+        .named
+        .iter()
+        .filter(|f| {
+            from_fields.named.iter().any(|g| {
+                g.ident == f.ident && g.ty.to_token_stream().to_string() == f.ty.to_token_stream().to_string()
+            })
+        })
+        .map(|f| f.ident.as_ref().unwrap())
+        .collect();
+    if shared.is_empty() {
+        return None;
     }
+    let previous: Vec<_> = shared
+        .iter()
+        .map(|ident| Ident::new(&format!("previous_{ident}"), Span::call_site()))
+        .collect();
+    let (from_ident, to_ident) = (&from.ident, &to.ident);
+    Some(quote! {
+        (#struct_name::#from_ident { #(#shared: #previous,)* .. }, #struct_name::#to_ident { #(#shared,)* .. }) => {
+            #(*#shared = #previous;)*
+        }
+    })
 }
 
-fn variant_inspect_arm(variant: &Variant, struct_name: &Ident, mutable: bool) -> TokenStream {
+fn variant_inspect_arm(
+    variant: &Variant,
+    struct_name: &Ident,
+    mutable: bool,
+    groups: groups::GroupStyle,
+) -> TokenStream {
     let ident = &variant.ident;
-    match &variant.fields {
-        Fields::Named(fields) => {
-            let field_idents: Vec<_> = fields
-                .clone()
-                .named
-                .iter()
-                .map(|f| {
-                    let ident = f.ident.clone();
-                    quote!( #ident )
-                })
-                .collect();
-            // TODO: properly refer to trait
-            let inspect_fields = fields
-                .named
-                .iter()
-                .map(|f| handle_named_field(f, mutable, true));
-            quote!(#struct_name::#ident { #(#field_idents),* } => { #(#inspect_fields;)* })
+    let fields = match &variant.fields {
+        Fields::Named(fields) => fields.clone(),
+        Fields::Unnamed(fields) => unnamed_as_named(fields, |_| String::new()),
+        Fields::Unit => return quote!(#struct_name::#ident => {} ),
+    };
+    let field_idents: Vec<_> = fields.named.iter().map(|f| &f.ident).collect();
+    let pattern = match &variant.fields {
+        Fields::Unnamed(_) => quote!(#struct_name::#ident ( #(#field_idents),* )),
+        _ => quote!(#struct_name::#ident { #(#field_idents),* }),
+    };
+    // NOTE: the conditions may refer to `self`, so the fields are only bound once they are
+    // evaluated
+    let conditions = variant_conditions(&fields, &pattern);
+    let (pattern, bind) = match conditions.is_empty() {
+        true => (pattern, quote!()),
+        false => {
+            let any = match &variant.fields {
+                Fields::Unnamed(_) => quote!(#struct_name::#ident(..)),
+                _ => quote!(#struct_name::#ident { .. }),
+            };
+            let bind = quote! {
+                #(#conditions)*
+                #[allow(unused_variables, irrefutable_let_patterns)]
+                let #pattern = self else { unreachable!() };
+            };
+            (any, bind)
         }
-        Fields::Unnamed(fields) => {
-            let field_idents: Vec<_> = (0..fields.unnamed.len()).map(|i| Ident::new(format!("unnamed_{i}").as_str(), Span::call_site())).collect();
-            let inspect_fields = field_idents.iter().map(|id| {
-                if mutable {
-                    quote! {#id.inspect_mut("", ui)}
-                } else {
-                    quote! {#id.inspect("", ui)}
-                }
+    };
+    // NOTE: hidden fields are bound regardless
+    if let Some(inspect) = groups::grouped_fields(&fields, groups, mutable, true, false) {
+        return match mutable {
+            true => quote!(#[allow(unused_variables)] #pattern => { #bind response |= #inspect; }),
+            false => quote!(#[allow(unused_variables)] #pattern => { #bind #inspect; }),
+        };
+    }
+    let inspect_fields = fields
+        .named
+        .iter()
+        .filter_map(|f| handle_named_field(f, &fields, mutable, true));
+    quote!(#[allow(unused_variables)] #pattern => { #bind #(#inspect_fields)* })
+}
+
+/// Statements evaluating the `visible_if`/`enabled_if` conditions of the fields of the variant (or
+/// tuple struct) matching `pattern` (see [variant_condition]), with its fields bound by reference
+fn variant_conditions(fields: &FieldsNamed, pattern: &TokenStream) -> Vec<TokenStream> {
+    let mut conditions = vec![];
+    for f in &fields.named {
+        let attr = FieldAttr::from_field(f).expect("Could not get attributes from field");
+        for (kind, condition) in [("visible", &attr.visible_if), ("enabled", &attr.enabled_if)] {
+            let Some(condition) = condition else {
+                continue;
+            };
+            let ident = variant_condition(f, kind);
+            let expr = parse_expr(condition);
+            conditions.push(quote! {
+                #[allow(unreachable_patterns, unused_variables)]
+                let #ident: bool = match &*self {
+                    #pattern => #expr,
+                    _ => unreachable!(),
+                };
             });
-            quote!(#struct_name::#ident (#(#field_idents),*) => { #(#inspect_fields;)* })
         }
-        Fields::Unit => {
-            quote!(#struct_name::#ident => () )
+    }
+    conditions
+}
+
+/// Variable holding the `kind` (`visible` or `enabled`) condition of a field of an enum variant
+/// (or tuple struct), as evaluated by [variant_conditions] before the fields are drawn
+pub(crate) fn variant_condition(f: &Field, kind: &str) -> Ident {
+    Ident::new(
+        &format!("{kind}_{}", f.ident.as_ref().unwrap()),
+        Span::call_site(),
+    )
+}
+
+/// The fields of a tuple struct or variant as named fields, named after their bindings
+/// (`unnamed_i`) and labelled by `default_label` unless given a `name`
+fn unnamed_as_named(fields: &FieldsUnnamed, default_label: impl Fn(usize) -> String) -> FieldsNamed {
+    let named = fields.unnamed.iter().enumerate().map(|(i, f)| {
+        let mut f = f.clone();
+        f.ident = Some(utils::unnamed_binding(i));
+        let attr = FieldAttr::from_field(&f).expect("Could not get attributes from field");
+        if attr.name.is_none() {
+            let label = default_label(i);
+            f.attrs.push(parse_quote!(#[inspect(name = #label)]));
         }
+        f
+    });
+    FieldsNamed {
+        brace_token: Default::default(),
+        named: named.collect(),
     }
 }
 
-fn handle_fields(fields: &Fields, mutable: bool) -> TokenStream {
+fn handle_fields(fields: &Fields, mutable: bool, groups: groups::GroupStyle) -> TokenStream {
     match fields {
-        Fields::Named(ref fields) => handle_named_fields(fields, mutable),
-        Fields::Unnamed(ref fields) => handle_unnamed_fields(fields, mutable),
+        Fields::Named(ref fields) => groups::grouped_fields(fields, groups, mutable, false, false)
+            .unwrap_or_else(|| handle_named_fields(fields, mutable)),
+        Fields::Unnamed(ref fields) => handle_unnamed_fields(fields, mutable, groups),
         // Empty implementation for unit fields (needed in plain enum variant for instance)
-        Fields::Unit => quote!(),
+        Fields::Unit => collect_field_inspects(vec![], mutable),
     }
 }
 
-fn handle_named_field(f: &Field, mutable: bool, loose: bool) -> TokenStream {
+/// Gathers field inspect expressions into a block, which for `inspect_mut` evaluates to the union
+/// of their responses.
+fn collect_field_inspects(field_inspects: Vec<TokenStream>, mutable: bool) -> TokenStream {
+    if mutable {
+        quote! {{
+            let mut response = egui_inspect::utils::empty_response(ui);
+            #(#field_inspects)*
+            response
+        }}
+    } else {
+        quote! {{
+            #(#field_inspects)*
+        }}
+    }
+}
+
+/// Inspect statement for a field, which accumulates into `response` if mutable (`None` if hidden)
+fn handle_named_field(
+    f: &Field,
+    siblings: &FieldsNamed,
+    mutable: bool,
+    loose: bool,
+) -> Option<TokenStream> {
     let attr = FieldAttr::from_field(f).expect("Could not get attributes from field");
 
     if attr.hide {
-        return quote!();
+        return None;
+    }
+
+    let field_mutable = mutable && !attr.no_edit;
+    let name_str = field_name(f, &attr);
+    let inspect = named_field_inspect(f, &attr, field_mutable, loose, &quote!(#name_str));
+    let check = validation_check(f, &attr, loose);
+
+    let contains_match = contains_match(f, &attr, loose);
+    let id_salt = f.ident.as_ref().unwrap().to_string();
+    let mut scope = documented(
+        f,
+        &name_str,
+        field_scope(quote!(#id_salt), inspect, check, field_mutable),
+    );
+    if let Some(enabled_if) = &attr.enabled_if {
+        let enabled = match loose {
+            true => variant_condition(f, "enabled").into_token_stream(),
+            false => field_condition(enabled_if, siblings, false),
+        };
+        scope = quote!(ui.add_enabled_ui(#enabled, |ui| #scope).inner);
+    }
+
+    // NOTE: the field is skipped while hidden by a field filter
+    // (flattened fields have no label to match)
+    let filter_label = match attr.flatten {
+        true => "",
+        false => name_str.as_str(),
+    };
+    let statement = match mutable {
+        true => quote! {{
+            let contains_match = #contains_match;
+            if let Some(field_response) = egui_inspect::filter::show_field(ui, #filter_label, contains_match, |ui| #scope) {
+                response |= field_response;
+            }
+        }},
+        false => quote! {{
+            let contains_match = #contains_match;
+            egui_inspect::filter::show_field(ui, #filter_label, contains_match, |ui| {
+                #scope;
+            });
+        }},
+    };
+    Some(match &attr.visible_if {
+        Some(visible_if) => {
+            let visible = match loose {
+                true => variant_condition(f, "visible").into_token_stream(),
+                false => field_condition(visible_if, siblings, false),
+            };
+            quote!(if #visible #statement)
+        }
+        None => statement,
+    })
+}
+
+/// Evaluates a `visible_if`/`enabled_if` expression to a bool, with the sibling fields of a struct
+/// bound by reference (`loose` variant fields are bound already, by reference). Conditions of
+/// variants drawn by `inspect_mut` are evaluated up front instead, see [variant_conditions].
+pub(crate) fn field_condition(expr: &str, siblings: &FieldsNamed, loose: bool) -> TokenStream {
+    let expr = parse_expr(expr);
+    if loose {
+        return quote!({ #expr });
+    }
+    let names = siblings.named.iter().map(|f| &f.ident);
+    quote!({
+        #[allow(unused_variables)]
+        let Self { #(#names),* } = &*self;
+        #expr
+    })
+}
+
+/// Wraps the field's scope (see [field_scope]) to show its doc comment, if any
+fn documented(f: &Field, name_str: &str, scope: TokenStream) -> TokenStream {
+    match utils::doc_comment(&f.attrs) {
+        Some(doc) => quote!(egui_inspect::help::with_doc(ui, #name_str, #doc, |ui| #scope)),
+        None => scope,
+    }
+}
+
+/// Expression of whether the active field filter matches something nested in the field
+fn contains_match(f: &Field, attr: &FieldAttr, loose: bool) -> TokenStream {
+    if attr.custom_func.is_some() || attr.custom_func_mut.is_some() {
+        return quote!(false);
     }
+    let name = &f.ident;
+    match loose {
+        true => quote!(egui_inspect::filter::contains_match(ui, &*#name)),
+        false => quote!(egui_inspect::filter::contains_match(ui, &self.#name)),
+    }
+}
 
-    let mutable = mutable && !attr.no_edit;
+/// Label of a named field
+fn field_name(f: &Field, attr: &FieldAttr) -> String {
+    attr.name
+        .clone()
+        .unwrap_or_else(|| f.ident.as_ref().unwrap().to_string())
+}
 
-    if let Some(ts) = handle_custom_func(f, mutable, &attr) {
-        return ts;
+/// Inspect expression of a named field, drawn with the (`&str`) `label` expression
+fn named_field_inspect(
+    f: &Field,
+    attr: &FieldAttr,
+    field_mutable: bool,
+    loose: bool,
+    label: &TokenStream,
+) -> TokenStream {
+    if attr.flatten {
+        return flatten_inspect(f, field_mutable, loose);
     }
+    handle_custom_func(f, field_mutable, attr, loose, label)
+        .or_else(|| internal_paths::try_handle_internal_path(f, field_mutable, attr, loose, label))
+        .unwrap_or_else(|| utils::get_default_function_call(f, field_mutable, attr, loose, label))
+}
 
-    if let Some(ts) = internal_paths::try_handle_internal_path(f, mutable, &attr, loose) {
-        return ts;
+/// Inspect expression of a `flatten` field, drawing its contents only (see
+/// [egui_inspect::EguiInspect::inspect_flat])
+fn flatten_inspect(f: &Field, field_mutable: bool, loose: bool) -> TokenStream {
+    let name = &f.ident;
+    match (field_mutable, loose) {
+        (true, true) => quote!(egui_inspect::EguiInspect::inspect_flat_mut(#name, ui)),
+        (true, false) => quote!(egui_inspect::EguiInspect::inspect_flat_mut(&mut self.#name, ui)),
+        (false, true) => quote!(egui_inspect::EguiInspect::inspect_flat(#name, ui)),
+        (false, false) => quote!(egui_inspect::EguiInspect::inspect_flat(&self.#name, ui)),
     }
+}
+
+/// Expression evaluating the field's `validate` function, if any
+fn validation_check(f: &Field, attr: &FieldAttr, loose: bool) -> Option<TokenStream> {
+    attr.validate.as_ref().map(|validate| {
+        let validate = parse_path(validate);
+        let name = &f.ident;
+        match loose {
+            true => quote!(#validate(#name)),
+            false => quote!(#validate(&self.#name)),
+        }
+    })
+}
 
-    utils::get_default_function_call(f, mutable, &attr, loose)
+/// Expression drawing the field within its own id scope (so widget state is keyed by the field
+/// path), evaluating to the response returned by `inspect` if `returns_response`, otherwise to that
+/// of the scope (e.g. for fields not edited due to `no_edit`). If given, `check` evaluates the
+/// field's validation result, an error is displayed around/under the field.
+fn field_scope(
+    id_salt: TokenStream,
+    inspect: TokenStream,
+    check: Option<TokenStream>,
+    returns_response: bool,
+) -> TokenStream {
+    let response = match returns_response {
+        true => quote!(inner),
+        false => quote!(response),
+    };
+    let Some(check) = check else {
+        return quote!(ui.push_id(#id_salt, |ui| #inspect).#response);
+    };
+    quote! {{
+        let scope = ui.push_id(#id_salt, |ui| #inspect);
+        if let Err(message) = #check {
+            egui_inspect::validation::show_validation_error(ui, scope.response.rect, &message);
+        }
+        scope.#response
+    }}
+}
+
+/// Grid of the `fields` (a subset of the struct's `siblings`), see [egui_inspect::grid]
+fn handle_grid_fields(fields: &[&Field], siblings: &FieldsNamed, mutable: bool) -> TokenStream {
+    // NOTE: conditions are evaluated up front, as the rows borrow the fields until drawn
+    let mut conditions = vec![];
+    let mut rows = vec![];
+    for (i, f) in fields.iter().enumerate() {
+        let attr = FieldAttr::from_field(f).expect("Could not get attributes from field");
+        let enabled = attr.enabled_if.as_ref().map(|enabled_if| {
+            let ident = Ident::new(&format!("enabled_{i}"), Span::call_site());
+            let condition = field_condition(enabled_if, siblings, false);
+            conditions.push(quote!(let #ident = #condition;));
+            ident
+        });
+        let Some(row) = grid_row(f, mutable, enabled) else {
+            continue;
+        };
+        rows.push(match &attr.visible_if {
+            Some(visible_if) => {
+                let ident = Ident::new(&format!("visible_{i}"), Span::call_site());
+                let condition = field_condition(visible_if, siblings, false);
+                conditions.push(quote!(let #ident = #condition;));
+                quote!(if #ident { rows.push(#row); })
+            }
+            None => quote!(rows.push(#row);),
+        });
+    }
+    let rows = quote! {
+        #(#conditions)*
+        #[allow(unused_mut)]
+        let mut rows = vec![];
+        #(#rows)*
+    };
+    match mutable {
+        true => quote!({
+            #rows
+            egui_inspect::grid::grid_rows(ui, rows)
+        }),
+        false => quote!({
+            #rows
+            egui_inspect::grid::grid_rows(ui, rows);
+        }),
+    }
+}
+
+/// [egui_inspect::grid::GridRow] of a named field (`None` if hidden)
+fn grid_row(f: &Field, mutable: bool, enabled: Option<Ident>) -> Option<TokenStream> {
+    let attr = FieldAttr::from_field(f).expect("Could not get attributes from field");
+
+    if attr.hide {
+        return None;
+    }
+
+    let name = &f.ident;
+    let field_mutable = mutable && !attr.no_edit;
+    let name_str = field_name(f, &attr);
+    let custom = attr.custom_func.is_some() || attr.custom_func_mut.is_some();
+    // NOTE: custom functions may take a `&'static str` label, and need not be for an EguiInspect
+    // type, they are assumed to fit on a line. Buttons are labelled by the field name instead.
+    let (row_label, label, inline) = if attr.flatten {
+        ("", quote!(""), quote!(false))
+    } else if custom {
+        (name_str.as_str(), quote!(""), quote!(true))
+    } else if attr.button {
+        ("", quote!(#name_str), quote!(true))
+    } else {
+        (
+            name_str.as_str(),
+            quote!(field_label),
+            quote!(egui_inspect::EguiInspect::is_inline(&self.#name)),
+        )
+    };
+    let inspect = named_field_inspect(f, &attr, field_mutable, false, &label);
+    let check = validation_check(f, &attr, false);
+    let id_salt = name.as_ref().unwrap().to_string();
+    let mut scope = documented(
+        f,
+        &name_str,
+        field_scope(quote!(#id_salt), inspect, check, field_mutable),
+    );
+    if let Some(enabled) = enabled {
+        scope = quote!(ui.add_enabled_ui(#enabled, |ui| #scope).inner);
+    }
+    let doc = match utils::doc_comment(&f.attrs) {
+        Some(doc) => quote!(Some(#doc)),
+        None => quote!(None),
+    };
+    let contains_match = match attr.button {
+        true => quote!(egui_inspect::filter::label_matches_active(ui, #name_str)),
+        false => contains_match(f, &attr, false),
+    };
+
+    Some(quote! {
+        egui_inspect::grid::GridRow {
+            label: #row_label,
+            inline: #inline,
+            contains_match: #contains_match,
+            doc: #doc,
+            inspect: Box::new(|ui: &mut egui_inspect::egui::Ui, field_label: &str| {
+                #[allow(unused_variables)]
+                let field_label = field_label;
+                #scope
+            }),
+        }
+    })
+}
+
+/// Path given as an attribute (see [typed_attrs]), already checked by [checks::check_input]
+fn parse_path(path: &str) -> syn::Path {
+    typed_attrs::code_tokens(path)
+        .ok()
+        .and_then(|tokens| syn::parse2(tokens).ok())
+        .unwrap_or_else(|| panic!("Could not parse path: {path}"))
+}
+
+/// Expression given as an attribute (see [typed_attrs]), already checked by
+/// [checks::check_input]
+fn parse_expr(expr: &str) -> syn::Expr {
+    typed_attrs::code_tokens(expr)
+        .ok()
+        .and_then(|tokens| syn::parse2(tokens).ok())
+        .unwrap_or_else(|| panic!("Could not parse expression: {expr}"))
 }
 
 fn handle_named_fields(fields: &FieldsNamed, mutable: bool) -> TokenStream {
     let field_inspects = fields
         .named
         .iter()
-        .map(|f| handle_named_field(f, mutable, false));
-    quote! {
-        #(#field_inspects)*
+        .filter_map(|f| handle_named_field(f, fields, mutable, false))
+        .collect();
+    collect_field_inspects(field_inspects, mutable)
+}
+
+/// Fields of a tuple struct, bound by reference so that they are drawn as (loose) named fields
+fn handle_unnamed_fields(
+    fields: &FieldsUnnamed,
+    mutable: bool,
+    groups: groups::GroupStyle,
+) -> TokenStream {
+    let fields = unnamed_as_named(fields, |i| format!("Field {i}"));
+    let bindings: Vec<_> = fields.named.iter().map(|f| &f.ident).collect();
+    // NOTE: as for enum variants, the conditions are evaluated before the fields are bound
+    let conditions = variant_conditions(&fields, &quote!(Self( #(#bindings),* )));
+    let inspect = groups::grouped_fields(&fields, groups, mutable, true, false).unwrap_or_else(|| {
+        let field_inspects = fields
+            .named
+            .iter()
+            .filter_map(|f| handle_named_field(f, &fields, mutable, true))
+            .collect();
+        collect_field_inspects(field_inspects, mutable)
+    });
+    quote!({
+        #(#conditions)*
+        #[allow(unused_variables)]
+        let Self( #(#bindings),* ) = self;
+        #inspect
+    })
+}
+
+/// Inspect of a `transparent` struct, drawn as its only (non hidden) field with the struct's label
+fn transparent_inspect(data: &Data, mutable: bool) -> TokenStream {
+    let (fields, pattern) = transparent_field(data);
+    let attr = FieldAttr::from_field(&fields).expect("Could not get attributes from field");
+    let field_mutable = mutable && !attr.no_edit;
+    let inspect = named_field_inspect(&fields, &attr, field_mutable, true, &quote!(label));
+    match mutable && !field_mutable {
+        true => quote!({
+            #pattern
+            ui.scope(|ui| #inspect).response
+        }),
+        false => quote!({
+            #pattern
+            #inspect
+        }),
     }
 }
 
-fn handle_unnamed_fields(fields: &FieldsUnnamed, mutable: bool) -> TokenStream {
-    let mut field_inspects = Vec::new();
-    for (i, _) in fields.unnamed.iter().enumerate() {
-        let tuple_index = Index::from(i);
-        let name = format!("Field {i}");
-        let ref_str = if mutable { quote!(&mut) } else { quote!(&) };
-        field_inspects.push(
-            quote! { egui_inspect::EguiInspect::inspect(#ref_str self.#tuple_index, #name, ui);},
-        );
+/// `is_inline` of a `transparent` struct, that of its field
+fn transparent_is_inline(data: &Data) -> TokenStream {
+    let (field, pattern) = transparent_field(data);
+    let attr = FieldAttr::from_field(&field).expect("Could not get attributes from field");
+    if attr.custom_func.is_some() || attr.custom_func_mut.is_some() {
+        return quote!(true);
     }
+    let name = &field.ident;
+    quote!({
+        #pattern
+        egui_inspect::EguiInspect::is_inline(#name)
+    })
+}
 
-    let result = quote! {
-        #(#field_inspects)*
+/// The only (non hidden) field of a `transparent` struct, named as in [unnamed_as_named], and the
+/// statement binding it from `self`
+fn transparent_field(data: &Data) -> (Field, TokenStream) {
+    let Data::Struct(data) = data else {
+        unreachable!("transparent enums are rejected by checks::check_input");
     };
-    result
+    let (fields, pattern) = match &data.fields {
+        Fields::Named(fields) => {
+            let names = fields.named.iter().map(|f| &f.ident);
+            (fields.clone(), quote!(Self { #(#names),* }))
+        }
+        Fields::Unnamed(fields) => {
+            let fields = unnamed_as_named(fields, |_| String::new());
+            let names = fields.named.iter().map(|f| &f.ident);
+            let pattern = quote!(Self( #(#names),* ));
+            (fields, pattern)
+        }
+        Fields::Unit => unreachable!("transparent unit structs are rejected by checks::check_input"),
+    };
+    let mut shown = fields.named.into_iter().filter(|f| {
+        !FieldAttr::from_field(f)
+            .expect("Could not get attributes from field")
+            .hide
+    });
+    let (Some(field), None) = (shown.next(), shown.next()) else {
+        unreachable!("transparent structs are checked to have one shown field");
+    };
+    (field, quote!(#[allow(unused_variables)] let #pattern = self;))
 }
 
-fn handle_custom_func(field: &Field, mutable: bool, attrs: &FieldAttr) -> Option<TokenStream> {
+fn handle_custom_func(
+    field: &Field,
+    mutable: bool,
+    attrs: &FieldAttr,
+    loose: bool,
+    label: &TokenStream,
+) -> Option<TokenStream> {
     let name = &field.ident;
-
-    let name_str = match &attrs.name {
-        Some(n) => n.clone(),
-        None => name.clone().unwrap().to_string(),
+    let (base, base_mut) = match loose {
+        true => (quote!(#name), quote!(#name)),
+        false => (quote!(&self.#name), quote!(&mut self.#name)),
     };
 
     if let Some(custom_func_mut) = attrs.custom_func_mut.as_ref() {
         if mutable && !attrs.no_edit  {
-            let ident = syn::Path::from_string(custom_func_mut)
-                .unwrap_or_else(|_| panic!("Could not find function: {}", custom_func_mut));
+            let ident = parse_path(custom_func_mut);
             return Some(quote_spanned! { field.span() => {
-                    #ident(&mut self.#name, &#name_str, ui);
+                    let scope = ui.scope(|ui| #ident(#base_mut, &#label, ui));
+                    egui_inspect::InspectReturn::into_response(scope.inner, scope.response)
                 }
             });
         }
@@ -614,10 +1629,17 @@ fn handle_custom_func(field: &Field, mutable: bool, attrs: &FieldAttr) -> Option
 
     if let Some(custom_func) = attrs.custom_func.as_ref() {
         // TODO: Applicable conditions?
-        let ident = syn::Path::from_string(custom_func)
-            .unwrap_or_else(|_| panic!("Could not find function: {}", custom_func));
+        let ident = parse_path(custom_func);
+        if mutable {
+            return Some(quote_spanned! { field.span() => {
+                    ui.scope(|ui| {
+                        #ident(#base, &#label, ui);
+                    }).response
+                }
+            });
+        }
         return Some(quote_spanned! { field.span() => {
-                #ident(&self.#name, &#name_str, ui);
+                #ident(#base, &#label, ui);
             }
         });
     }
@@ -0,0 +1,121 @@
+//! Attribute options holding code (paths and expressions) may be given as Rust tokens, e.g.
+//! `#[inspect(frame_style = crate::CUSTOM_BOX)]`, rather than as a string. As darling (through
+//! `syn::Meta`) only accepts literal values, such tokens are turned into their string form before
+//! the attributes are parsed, and kept aside so that the generated code uses the original tokens
+//! (keeping their spans, for errors and IDE navigation). The tokens are only kept for the
+//! duration of the derive (see [TypedValues]), as `proc_macro` handles are invalid in any other.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+use proc_macro2::{TokenStream, TokenTree};
+use quote::ToTokens;
+use syn::parse::ParseStream;
+use syn::spanned::Spanned;
+use syn::{Attribute, Data, DeriveInput, Expr, Lit, LitStr, Token};
+
+/// Options whose values are code
+const CODE_OPTIONS: [&str; 10] = [
+    "custom_func",
+    "custom_func_mut",
+    "validate",
+    "visible_if",
+    "enabled_if",
+    "frame_style",
+    "ctor",
+    "options",
+    "init",
+    "post_inspect",
+];
+
+thread_local! {
+    /// Original tokens of the code options given as tokens, by their string form
+    static TYPED_VALUES: RefCell<HashMap<String, TokenStream>> = RefCell::new(HashMap::new());
+}
+
+/// Guard of the tokens kept by [stringify_typed_values], dropping them at the end of the derive
+#[must_use]
+pub(crate) struct TypedValues;
+
+impl Drop for TypedValues {
+    fn drop(&mut self) {
+        TYPED_VALUES.with(|values| values.borrow_mut().clear());
+    }
+}
+
+/// Replaces the code options given as tokens in the `attr_name` attributes of the input (on the
+/// item, its fields and variants) by their string form, their tokens being kept (for
+/// [code_tokens]) until the returned guard is dropped
+pub(crate) fn stringify_typed_values(input: &mut DeriveInput, attr_name: &str) -> TypedValues {
+    TYPED_VALUES.with(|values| values.borrow_mut().clear());
+    stringify_attrs(&mut input.attrs, attr_name);
+    match &mut input.data {
+        Data::Struct(data) => {
+            for f in data.fields.iter_mut() {
+                stringify_attrs(&mut f.attrs, attr_name);
+            }
+        }
+        Data::Enum(data) => {
+            for variant in data.variants.iter_mut() {
+                stringify_attrs(&mut variant.attrs, attr_name);
+                for f in variant.fields.iter_mut() {
+                    stringify_attrs(&mut f.attrs, attr_name);
+                }
+            }
+        }
+        Data::Union(data) => {
+            for f in data.fields.named.iter_mut() {
+                stringify_attrs(&mut f.attrs, attr_name);
+            }
+        }
+    }
+    TypedValues
+}
+
+/// Tokens of a code option, the original ones if it was given as tokens, else parsed from the
+/// string
+pub(crate) fn code_tokens(value: &str) -> Result<TokenStream, proc_macro2::LexError> {
+    match TYPED_VALUES.with(|values| values.borrow().get(value).cloned()) {
+        Some(tokens) => Ok(tokens),
+        None => value.parse(),
+    }
+}
+
+fn stringify_attrs(attrs: &mut [Attribute], attr_name: &str) {
+    for attr in attrs
+        .iter_mut()
+        .filter(|attr| attr.path.is_ident(attr_name))
+    {
+        // malformed attributes are left as they are, for darling to report
+        if let Ok(args) = attr.parse_args_with(stringify_args) {
+            attr.tokens = quote::quote!((#args));
+        }
+    }
+}
+
+/// `key = value` pairs (or other nested meta) separated by commas, with the code values turned
+/// into string literals (spanned as the code)
+fn stringify_args(input: ParseStream) -> syn::Result<TokenStream> {
+    let mut args = TokenStream::new();
+    while !input.is_empty() {
+        let key: syn::Path = input.call(syn::Path::parse_mod_style)?;
+        key.to_tokens(&mut args);
+        let is_code = CODE_OPTIONS.iter().any(|option| key.is_ident(option));
+        if is_code && input.peek(Token![=]) && !input.peek2(Lit) {
+            input.parse::<Token![=]>()?.to_tokens(&mut args);
+            let value: Expr = input.parse()?;
+            let tokens = value.to_token_stream();
+            let string = tokens.to_string();
+            LitStr::new(&string, value.span()).to_tokens(&mut args);
+            TYPED_VALUES.with(|values| values.borrow_mut().insert(string, tokens));
+        }
+        // anything else is kept as is, up to the next comma
+        while !input.is_empty() && !input.peek(Token![,]) {
+            input.parse::<TokenTree>()?.to_tokens(&mut args);
+        }
+        if !input.is_empty() {
+            input.parse::<Token![,]>()?.to_tokens(&mut args);
+        }
+    }
+    Ok(args)
+}
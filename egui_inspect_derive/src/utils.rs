@@ -1,8 +1,8 @@
-use proc_macro2::TokenStream;
+use proc_macro2::{Ident, Span, TokenStream};
 use quote::{quote, quote_spanned};
 use syn::spanned::Spanned;
 use syn::Type::{Path, Reference};
-use syn::{Field, Type};
+use syn::{Attribute, Field, Lit, Meta, Type};
 
 use crate::FieldAttr;
 
@@ -20,19 +20,38 @@ pub fn get_path_str(type_path: &Type) -> Option<String> {
     }
 }
 
+/// Binding of the `i`th field of a tuple struct or variant, in patterns of generated code
+pub(crate) fn unnamed_binding(i: usize) -> Ident {
+    Ident::new(format!("unnamed_{i}").as_str(), Span::call_site())
+}
+
+/// Text of the `///` doc comments among `attrs` (`None` if there are none)
+pub(crate) fn doc_comment(attrs: &[Attribute]) -> Option<String> {
+    let lines: Vec<_> = attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("doc"))
+        .filter_map(|attr| match attr.parse_meta() {
+            Ok(Meta::NameValue(meta)) => match meta.lit {
+                Lit::Str(doc) => Some(doc.value()),
+                _ => None,
+            },
+            _ => None,
+        })
+        .map(|line| line.strip_prefix(' ').map(str::to_string).unwrap_or(line))
+        .collect();
+    let doc = lines.join("\n").trim().to_string();
+    (!doc.is_empty()).then_some(doc)
+}
+
 pub(crate) fn get_default_function_call(
     field: &Field,
     mutable: bool,
     attrs: &FieldAttr,
     loose_field: bool,
+    label: &TokenStream,
 ) -> TokenStream {
     let name = &field.ident;
 
-    let name_str = match &attrs.name {
-        Some(n) => n.clone(),
-        None => name.clone().unwrap().to_string(),
-    };
-
     let base = if loose_field {
         quote!(#name)
     } else if mutable {
@@ -41,15 +60,112 @@ pub(crate) fn get_default_function_call(
         quote!(&self.#name)
     };
 
-    if mutable {
+    if mutable && attrs.duplicate {
+        let options = inspect_options(attrs)
+            .unwrap_or_else(|| quote!(egui_inspect::InspectOptions::default()));
         quote_spanned! {field.span() => {
-            egui_inspect::EguiInspect::inspect_mut(#base, &#name_str, ui);
+            egui_inspect::vec_editor::inspect_mut_duplicating(#base, &#label, ui, &#options)
             }
         }
+    } else if mutable {
+        match inspect_options(attrs) {
+            Some(options) => quote_spanned! {field.span() => {
+                egui_inspect::EguiInspect::inspect_mut_with(#base, &#label, ui, &#options)
+                }
+            },
+            None => quote_spanned! {field.span() => {
+                egui_inspect::EguiInspect::inspect_mut(#base, &#label, ui)
+                }
+            },
+        }
     } else {
-        quote_spanned! {field.span() => {
-            egui_inspect::EguiInspect::inspect(#base, &#name_str, ui);
-            }
+        match inspect_options(attrs) {
+            Some(options) => quote_spanned! {field.span() => {
+                egui_inspect::EguiInspect::inspect_with(#base, &#label, ui, &#options);
+                }
+            },
+            None => quote_spanned! {field.span() => {
+                egui_inspect::EguiInspect::inspect(#base, &#label, ui);
+                }
+            },
         }
     }
 }
+
+/// Runtime [egui_inspect::InspectOptions] for the options set on the field (`None` if none are)
+fn inspect_options(attrs: &FieldAttr) -> Option<TokenStream> {
+    let mut options = vec![];
+    if attrs.slider {
+        options.push(quote!(slider: true));
+    }
+    if attrs.log_slider {
+        options.push(quote!(log_slider: true));
+    }
+    if let Some(min) = attrs.min {
+        let min = min.bound();
+        options.push(quote!(min: Some(#min)));
+    }
+    if let Some(max) = attrs.max {
+        let max = max.bound();
+        options.push(quote!(max: Some(#max)));
+    }
+    if let Some(step) = attrs.step {
+        let step = step.as_f64();
+        options.push(quote!(step: Some(#step)));
+    }
+    if let Some(speed) = attrs.speed {
+        let speed = speed.as_f64();
+        options.push(quote!(speed: Some(#speed)));
+    }
+    if let Some(prefix) = &attrs.prefix {
+        options.push(quote!(prefix: Some(#prefix.to_string())));
+    }
+    if let Some(suffix) = &attrs.suffix {
+        options.push(quote!(suffix: Some(#suffix.to_string())));
+    }
+    if let Some(decimals) = attrs.decimals {
+        options.push(quote!(decimals: Some(#decimals)));
+    }
+    if let Some(clamp) = attrs.clamp {
+        let clamp = Ident::new(&format!("{clamp:?}"), Span::call_site());
+        options.push(quote!(clamping: egui_inspect::egui::SliderClamping::#clamp));
+    }
+    if let Some(format) = attrs.format {
+        let format = Ident::new(&format!("{format:?}"), Span::call_site());
+        options.push(quote!(format: egui_inspect::options::NumberFormat::#format));
+    }
+    if attrs.multiline {
+        options.push(quote!(multiline: true));
+    }
+    if attrs.password {
+        options.push(quote!(password: true));
+    }
+    if let Some(hint) = &attrs.hint {
+        options.push(quote!(hint: Some(#hint.to_string())));
+    }
+    if let Some(max_len) = attrs.max_len {
+        options.push(quote!(max_len: Some(#max_len)));
+    }
+    if let Some(charset) = attrs.charset {
+        let charset = Ident::new(&format!("{charset:?}"), Span::call_site());
+        options.push(quote!(charset: Some(egui_inspect::options::Charset::#charset)));
+    }
+    if let Some(regex) = &attrs.regex {
+        options.push(quote!(regex: Some(#regex.to_string())));
+    }
+    if let Some(code) = &attrs.code {
+        options.push(quote!(code: Some(#code.to_string())));
+    }
+    if attrs.markdown {
+        options.push(quote!(markdown: true));
+    }
+    if options.is_empty() {
+        return None;
+    }
+    Some(quote! {
+        egui_inspect::InspectOptions {
+            #(#options,)*
+            ..Default::default()
+        }
+    })
+}
@@ -0,0 +1,171 @@
+use darling::FromField;
+use proc_macro2::{Ident, TokenStream};
+use quote::quote;
+use syn::{Data, Field, Fields, FieldsNamed};
+
+use crate::internal_paths::path_is_internally_handled;
+use crate::utils::{get_path_str, unnamed_binding};
+use crate::{field_condition, parse_path, DeriveAttr, FieldAttr};
+
+/// Generates `EguiInspect::validation_errors`, collecting the results of the `validate` functions
+/// of the struct, its fields and its nested values (nothing is generated if there are none)
+pub(crate) fn validation_errors_fn(data: &Data, name: &Ident, attr: &DeriveAttr) -> TokenStream {
+    let mut checks = match data {
+        Data::Struct(data) => fields_checks(&data.fields, false),
+        Data::Enum(data_enum) => {
+            let mut has_checks = false;
+            let arms: Vec<_> = data_enum
+                .variants
+                .iter()
+                .map(|v| {
+                    let ident = &v.ident;
+                    let checks = fields_checks(&v.fields, true);
+                    has_checks |= !checks.is_empty();
+                    match &v.fields {
+                        Fields::Named(fields) => {
+                            let bindings = fields.named.iter().map(|f| &f.ident);
+                            quote!(#name::#ident { #(#bindings),* } => { #checks })
+                        }
+                        Fields::Unnamed(fields) => {
+                            let bindings = (0..fields.unnamed.len()).map(unnamed_binding);
+                            quote!(#name::#ident ( #(#bindings),* ) => { #checks })
+                        }
+                        Fields::Unit => quote!(#name::#ident => {}),
+                    }
+                })
+                .collect();
+            match has_checks {
+                true => quote! {
+                    #[allow(unused_variables)]
+                    match self {
+                        #(#arms),*
+                    }
+                },
+                false => quote!(),
+            }
+        }
+        Data::Union(_) => unreachable!("unions are rejected by checks::check_input"),
+    };
+
+    if let Some(validate) = &attr.validate {
+        let validate = parse_path(validate);
+        checks = quote! {
+            #checks
+            if let Err(message) = #validate(self) {
+                errors.push(egui_inspect::validation::ValidationError::new("", message));
+            }
+        };
+    }
+
+    if checks.is_empty() {
+        return quote!();
+    }
+
+    quote! {
+        fn validation_errors(&self) -> Vec<egui_inspect::validation::ValidationError> {
+            let mut errors = vec![];
+            #checks
+            errors
+        }
+    }
+}
+
+/// Checks of all fields, `loose` fields are bound by reference (within enum variant arms)
+fn fields_checks(fields: &Fields, loose: bool) -> TokenStream {
+    match fields {
+        Fields::Named(named) => named
+            .named
+            .iter()
+            .map(|f| {
+                let name = &f.ident;
+                let access = match loose {
+                    true => quote!(#name),
+                    false => quote!(&self.#name),
+                };
+                named_field_checks(f, access, named, loose)
+            })
+            .collect(),
+        Fields::Unnamed(fields) => fields
+            .unnamed
+            .iter()
+            .enumerate()
+            .map(|(i, f)| {
+                let attr = FieldAttr::from_field(f).expect("Could not get attributes from field");
+                if attr.hide {
+                    return quote!();
+                }
+                let access = match loose {
+                    true => {
+                        let binding = unnamed_binding(i);
+                        quote!(#binding)
+                    }
+                    false => {
+                        let index = syn::Index::from(i);
+                        quote!(&self.#index)
+                    }
+                };
+                let segment = attr.name.clone().unwrap_or_else(|| i.to_string());
+                field_checks(f, &attr, access, &segment)
+            })
+            .collect(),
+        Fields::Unit => quote!(),
+    }
+}
+
+fn named_field_checks(
+    f: &Field,
+    access: TokenStream,
+    siblings: &FieldsNamed,
+    loose: bool,
+) -> TokenStream {
+    let attr = FieldAttr::from_field(f).expect("Could not get attributes from field");
+    if attr.hide {
+        return quote!();
+    }
+
+    let segment = attr
+        .name
+        .clone()
+        .unwrap_or_else(|| f.ident.as_ref().unwrap().to_string());
+
+    let checks = field_checks(f, &attr, access, &segment);
+    // hidden fields can't be corrected, so their errors are not reported
+    match &attr.visible_if {
+        Some(visible_if) if !checks.is_empty() => {
+            let visible = field_condition(visible_if, siblings, loose);
+            quote!(if #visible { #checks })
+        }
+        _ => checks,
+    }
+}
+
+/// Checks of the field's `validate` function and of its nested values, reported under `segment`
+fn field_checks(f: &Field, attr: &FieldAttr, access: TokenStream, segment: &str) -> TokenStream {
+    let mut checks = quote!();
+    if let Some(validate) = &attr.validate {
+        let validate = parse_path(validate);
+        checks = quote! {
+            if let Err(message) = #validate(#access) {
+                errors.push(egui_inspect::validation::ValidationError::new(#segment, message));
+            }
+        };
+    }
+
+    // fields drawn by custom functions need not be EguiInspect, and primitives have no errors
+    let custom = attr.custom_func.is_some() || attr.custom_func_mut.is_some();
+    let primitive = get_path_str(&f.ty).is_some_and(|p| path_is_internally_handled(&p));
+    if !custom && !primitive {
+        let nested = nested_checks(access, segment);
+        checks = quote!(#checks #nested);
+    }
+    checks
+}
+
+fn nested_checks(access: TokenStream, segment: &str) -> TokenStream {
+    quote! {
+        errors.extend(egui_inspect::validation::prefix_errors(
+            egui_inspect::EguiInspect::validation_errors(#access),
+            #segment,
+        ));
+    }
+}
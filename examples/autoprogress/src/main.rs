@@ -1,10 +1,12 @@
 use egui_inspect::{
     background_task::{BackgroundTask, Progress, SynchedStatsOpts, Task},
-    EframeMain, EguiInspect, DPEQ,
+    egui,
+    markdown::Markdown,
+    EframeMain, EguiInspect,
 };
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
-#[derive(EguiInspect, Clone, DPEQ, Default)]
+#[derive(EguiInspect, Clone, Default)]
 enum Mode {
     #[default]
     Ordinary,
@@ -15,8 +17,10 @@ enum Mode {
 }
 
 #[derive(EguiInspect, better_default::Default)]
+#[inspect(validate = "MySummation::fits_u32")]
 struct MySummation {
     #[default(100)]
+    #[inspect(validate = "nonzero")]
     iters: usize,
     #[default(25)]
     sleep_millis: u64,
@@ -25,6 +29,26 @@ struct MySummation {
     begin: bool,
 }
 
+fn nonzero(x: &usize) -> Result<(), String> {
+    match *x {
+        0 => Err("must be at least 1".into()),
+        _ => Ok(()),
+    }
+}
+
+impl MySummation {
+    fn fits_u32(&self) -> Result<(), String> {
+        let p = match self.mode {
+            Mode::Ordinary => 1,
+            Mode::Power { p } => p,
+        };
+        (0..self.iters as u32)
+            .try_fold(0u32, |sum, i| sum.checked_add(i.checked_pow(p)?))
+            .map(|_| ())
+            .ok_or_else(|| "the sum would overflow a u32".into())
+    }
+}
+
 impl Task for MySummation {
     type Return = u32;
     /// provide a SynchedStatsOpts object when ready to begin
@@ -54,19 +78,36 @@ struct Sleep5 {
 }
 
 impl Task for Sleep5 {
-    type Return = ();
+    type Return = Markdown;
     fn begin_signal(&self) -> Option<SynchedStatsOpts> {
         self.begin.then_some(Default::default())
     }
     fn on_exec(&mut self, _: Progress) -> Self::Return {
+        let start = Instant::now();
         std::thread::sleep(Duration::from_secs(5));
+        let slept = start.elapsed();
+        Markdown(format!(
+            "**Done**, slept for `{:.3} s`\n\
+            - requested: 5 s\n\
+            - overshoot: *{} µs*",
+            slept.as_secs_f64(),
+            slept.saturating_sub(Duration::from_secs(5)).as_micros()
+        ))
     }
 }
 
 #[derive(Default, EguiInspect, EframeMain)]
+#[eframe_main(post_inspect = "self.compare_results(ui);")]
 pub struct AutoProgressBarTest {
     background_task_1: BackgroundTask<MySummation>,
     background_task_2: BackgroundTask<MySummation>,
     #[inspect(name = "\"sleep for 5 sec task\"")]
     background_task_3: BackgroundTask<Sleep5, true>,
 }
+
+impl AutoProgressBarTest {
+    fn compare_results(&self, ui: &mut egui::Ui) {
+        self.background_task_1
+            .inspect_diff(&self.background_task_2, "task 1 vs task 2", ui);
+    }
+}
@@ -6,7 +6,7 @@ use egui_inspect::{
     },
     egui::{self, vec2, LayerId, Sense, Shape},
     logging::default_mixed_logger,
-    EframeMain, EguiInspect,
+    EframeMain, EguiInspect, InspectOptions,
 };
 use std::sync::Arc;
 use viewport_quad::ViewportQuad;
@@ -17,10 +17,14 @@ static FRAG_SHADER: &str = include_str!("../test_fragment.glsl");
 const ASPECT: f32 = 9.0 / 16.0;
 
 #[derive(EframeMain)]
-#[eframe_main(init = "FragViewport::init(_cc)")]
+#[eframe_main(init = FragViewport::init(cc))]
 struct FragViewport {
+    gl: Arc<glow::Context>,
     quad: ViewportQuad,
     t: f32,
+    /// fragment shader source, recompiled as it is edited
+    shader: String,
+    shader_error: Option<String>,
 }
 
 impl FragViewport {
@@ -29,9 +33,27 @@ impl FragViewport {
         let gl = cc.gl.as_ref().unwrap().clone();
         Self {
             quad: ViewportQuad::new(&gl, FRAG_SHADER),
+            gl,
             t: 0.0,
+            shader: FRAG_SHADER.to_string(),
+            shader_error: None,
         }
     }
+    fn shader_editor(&mut self, ui: &mut egui::Ui) -> egui::Response {
+        let options = InspectOptions {
+            code: Some("glsl".to_string()),
+            ..Default::default()
+        };
+        let response = self.shader.inspect_mut_with("", ui, &options);
+        if response.changed() {
+            // NOTE: the previous program is kept while the source does not compile
+            self.shader_error = self.quad.set_frag_shader(&self.gl, &self.shader).err();
+        }
+        if let Some(e) = &self.shader_error {
+            ui.colored_label(ui.visuals().error_fg_color, e);
+        }
+        response
+    }
     // TODO: make into widget?
     fn paint_viewport(&self, ui: &mut egui::Ui) {
         let available = ui.available_size();
@@ -67,12 +89,19 @@ impl FragViewport {
 }
 
 impl EguiInspect for FragViewport {
-    fn inspect_mut(&mut self, _: &str, ui: &mut egui::Ui) {
-        ui.horizontal(|ui| {
-            ui.label("uniform:");
-            ui.add(egui::Slider::new(&mut self.t, 0.0..=1.0));
-        });
+    fn inspect_mut(&mut self, _: &str, ui: &mut egui::Ui) -> egui::Response {
+        let mut response = ui
+            .horizontal(|ui| {
+                ui.label("uniform:");
+                ui.add(egui::Slider::new(&mut self.t, 0.0..=1.0))
+            })
+            .inner;
+        let editor = ui.collapsing("fragment shader", |ui| self.shader_editor(ui));
+        if let Some(edited) = editor.body_returned {
+            response |= edited;
+        }
         self.paint_viewport(ui);
         ui.label("a widget directly after the viewport...");
+        response
     }
 }
@@ -5,10 +5,10 @@ use egui_inspect::{
         log::{error, info, warn},
         LogsView,
     },
-    EframeMain, EguiInspect, DPEQ,
+    EframeMain, EguiInspect,
 };
 
-#[derive(EguiInspect, DPEQ, Default)]
+#[derive(EguiInspect, Default)]
 enum BasicLogSeverity {
     #[default]
     Info,
@@ -23,12 +23,16 @@ struct LogEmitter {
 }
 
 impl EguiInspect for LogEmitter {
-    fn inspect_mut(&mut self, _label: &str, ui: &mut egui_inspect::egui::Ui) {
+    fn inspect_mut(
+        &mut self,
+        _label: &str,
+        ui: &mut egui_inspect::egui::Ui,
+    ) -> egui_inspect::egui::Response {
         ui.separator();
         ui.label("Insert log entries:");
         ui.horizontal(|ui| {
-            self.severity.inspect_mut("severity", ui);
-            self.message.inspect_mut("", ui);
+            let response =
+                self.severity.inspect_mut("severity", ui) | self.message.inspect_mut("", ui);
             if ui.button("Log").clicked() {
                 match &self.severity {
                     BasicLogSeverity::Info => info!("{}", self.message),
@@ -36,7 +40,9 @@ impl EguiInspect for LogEmitter {
                     BasicLogSeverity::Error => error!("{}", self.message),
                 }
             }
-        });
+            response
+        })
+        .inner
     }
 }
 
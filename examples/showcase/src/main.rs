@@ -1,42 +1,91 @@
 use egui_inspect::egui::{self, Color32, Stroke, Style};
 use egui_inspect::egui_plot::{Line, Plot};
+use egui_inspect::filter::Searchable;
 use egui_inspect::search_select::SearchSelection;
+use egui_inspect::staged::Staged;
+use egui_inspect::undo::Undoable;
 use egui_inspect::{EframeMain, EguiInspect, FrameStyle, DEFAULT_FRAME_STYLE};
 use egui_inspect_wrap::VisualsUi;
 use std::collections::{BTreeMap, HashMap};
 
-#[derive(EguiInspect)]
-#[inspect(collapsible)]
+/// Fields of primitive types, laid out in a grid, with the numbers grouped in a section
+#[derive(EguiInspect, Clone)]
+#[inspect(collapsible, grid)]
 struct Primitives {
     #[inspect(no_edit)]
     string: String,
-    #[inspect(multiline)]
+    /// A hexadecimal id, only hex digits can be typed in, and it must be 8 of them
+    #[inspect(
+        charset = "hex",
+        max_len = 8,
+        regex = "[0-9a-fA-F]{8}",
+        hint = "8 hex digits"
+    )]
+    hex_id: String,
+    /// Only editable while the custom bool below is set
+    #[inspect(code = "toml", enabled_if = "*custom_bool")]
     code: String,
+    /// Rendered as Markdown, edited next to a live preview
+    #[inspect(markdown)]
+    notes: String,
+    /// Must be even, the error is shown inline while it is not
+    #[inspect(validate = is_even, group = "Numbers")]
     unsigned32: u32,
     #[inspect(hide)]
     _skipped: bool,
-    #[inspect(custom_func_mut = "custom_bool_inspect")]
+    /// A newtype drawn exactly like the number it wraps
+    distance: Meters,
+    #[inspect(custom_func_mut = custom_bool_inspect)]
     custom_bool: bool,
     raw_string: &'static str,
-    #[inspect(slider = false, min = 10.0, max = 125.0)]
+    #[inspect(min = 10, max = 125, format = "hex", prefix = "0x", group = "Numbers")]
     usize: usize,
-    #[inspect(slider, min = -43.0, max = 125.0)]
+    #[inspect(slider, min = -43.0, max = 125.0, group = "Numbers")]
     isize: isize,
-    #[inspect(log_slider, min = -43.0, max = 125.0)]
+    /// Drawn with a logarithmic slider, giving finer control near the minimum, and only shown
+    /// while isize is positive
+    #[inspect(
+        log_slider,
+        min = -43.0,
+        max = 125.0,
+        visible_if = *isize >= 0,
+        group = "Numbers"
+    )]
     log_varied_float64: f64,
 }
 
-fn custom_bool_inspect(boolean: &mut bool, label: &'static str, ui: &mut egui::Ui) {
+#[derive(EguiInspect, Clone)]
+#[inspect(transparent)]
+struct Meters(#[inspect(slider, min = 0.0, max = 10.0, step = 0.25, suffix = " m")] f64);
+
+fn custom_bool_inspect(
+    boolean: &mut bool,
+    label: &'static str,
+    ui: &mut egui::Ui,
+) -> egui::Response {
     ui.label("Overriden inspect for the following bool");
-    boolean.inspect(label, ui);
+    boolean.inspect_mut(label, ui)
+}
+
+fn is_even(x: &u32) -> Result<(), String> {
+    match x % 2 {
+        0 => Ok(()),
+        _ => Err(format!("{x} is not even")),
+    }
 }
 
 impl Default for Primitives {
     fn default() -> Self {
         Self {
             string: "I am a single line string".to_owned(),
-            code: "Hello\nI\nam\na\nmultiline\nstring".to_owned(),
+            hex_id: "c0ffee".to_owned(),
+            code: "[window]\ntitle = \"showcase\"\nsize = [800, 600]\n".to_owned(),
+            notes:
+                "## Notes\nSee the [egui docs](https://docs.rs/egui), **bold**, *italic*, `code`:\n\
+                - one\n- two"
+                    .to_owned(),
             _skipped: true,
+            distance: Meters(1.5),
             custom_bool: true,
             unsigned32: 42,
             raw_string: "YetAnotherString",
@@ -50,7 +99,7 @@ impl Default for Primitives {
 #[derive(EguiInspect)]
 #[inspect(collapsible)]
 struct Containers {
-    #[inspect(name = "vector")]
+    #[inspect(name = "vector", slider, min = 0, max = 30, duplicate)]
     an_ugly_internal_name: Vec<[f64; 2]>,
     string_map: HashMap<String, Custom>,
     ordered_string_map: BTreeMap<String, u32>,
@@ -103,13 +152,16 @@ static CUSTOM_BOX: FrameStyle = FrameStyle {
 
 #[derive(EguiInspect, PartialEq, Default, Debug)]
 #[inspect(
-    frame_style = "crate::CUSTOM_BOX",
+    frame_style = crate::CUSTOM_BOX,
     collapsible,
     on_hover_text = "show when hovering"
 )]
-struct Custom(i32, f32);
+struct Custom(
+    #[inspect(name = "integer")] i32,
+    #[inspect(name = "float")] f32,
+);
 
-#[derive(Default, PartialEq)]
+#[derive(Default)]
 struct MyPlot {
     stroke: Stroke,
     xy: Vec<[f64; 2]>,
@@ -123,18 +175,24 @@ impl EguiInspect for MyPlot {
         });
     }
 
-    fn inspect_mut(&mut self, label: &str, ui: &mut egui::Ui) {
-        self.stroke.inspect_mut("stroke", ui);
+    fn inspect_mut(&mut self, label: &str, ui: &mut egui::Ui) -> egui::Response {
+        let response = self.stroke.inspect_mut("stroke", ui);
         self.inspect(label, ui);
+        response
+    }
+    fn is_inline(&self) -> bool {
+        false
     }
 }
 
-#[derive(EguiInspect, PartialEq, Default)]
+#[derive(EguiInspect, Default)]
 #[inspect(collapsible)]
 enum MyEnum {
     #[default]
     PlainVariant,
-    UnnamedFieldVariant(usize, String),
+    /// Fields of tuple variants take the same attributes as named ones
+    #[inspect(name = "Unnamed fields")]
+    UnnamedFieldVariant(#[inspect(slider, min = 0, max = 10)] usize, String),
     VariantWithStructData {
         #[inspect(name = "Mirroring data in containers.vector, try editing it!")]
         my_plot: MyPlot,
@@ -144,13 +202,15 @@ enum MyEnum {
 
 #[derive(EguiInspect, Default, EframeMain)]
 #[inspect(no_border)]
-#[eframe_main(title = "My egui App", init = "MyApp::new()", no_eframe_app_derive)]
+#[eframe_main(title = "My egui App", init = MyApp::new(), no_eframe_app_derive)]
 struct MyApp {
     edit_style: bool,
     #[inspect(hide)]
-    visuals: VisualsUi,
-    some_primitives: Primitives,
-    containers: Containers,
+    visuals: Staged<VisualsUi>,
+    #[inspect(name = "some primitives (with undo history)")]
+    some_primitives: Undoable<Primitives>,
+    #[inspect(name = "containers (with field filter)")]
+    containers: Searchable<Containers>,
     fancy_enum: MyEnum,
 }
 
@@ -180,20 +240,25 @@ impl egui_inspect::eframe::App for MyApp {
                 .clone_from(&self.containers.an_ugly_internal_name);
         }
 
+        // doc comments of the focused (or hovered) field
+        egui_inspect::help::help_side_panel(ctx);
+
         egui::CentralPanel::default().show(ctx, |ui| {
             egui::ScrollArea::vertical().show(ui, |ui| {
                 ui.columns(2, |cols| {
                     // display own derived ui mutably
                     self.inspect_mut("", &mut cols[0]);
 
-                    // conditionally showing other ui based on interactions
-                    if self.edit_style {
-                        self.visuals
-                            .inspect_mut("visuals (egui style)", &mut cols[1]);
-
-                        // TODO: should ideally only set when changing
+                    // conditionally showing other ui based on interactions, the style is only
+                    // set once the staged edits are applied
+                    if self.edit_style
+                        && self
+                            .visuals
+                            .inspect_mut("visuals (egui style)", &mut cols[1])
+                            .changed()
+                    {
                         ctx.set_style(Style {
-                            visuals: self.visuals.clone().into(),
+                            visuals: (*self.visuals).clone().into(),
                             ..Default::default()
                         })
                     }
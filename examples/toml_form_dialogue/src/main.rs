@@ -49,14 +49,19 @@ struct TomlFormDialogue {
 }
 
 impl EguiInspect for TomlFormDialogue {
-    fn inspect_mut(&mut self, _label: &str, ui: &mut egui::Ui) {
+    fn inspect_mut(&mut self, _label: &str, ui: &mut egui::Ui) -> egui::Response {
         if let Some(message) = self.prompt.as_ref() {
             ui.label(message);
         }
-        self.inputs.inspect_mut("Options", ui);
-        if ui.button("Apply").clicked() {
+        let response = self.inputs.inspect_mut("Options", ui);
+        egui_inspect::validation::show_validation_errors(ui, &self.inputs.validation_errors());
+        if ui
+            .add_enabled(self.inputs.is_valid(), egui::Button::new("Apply"))
+            .clicked()
+        {
             print!("{}", toml::to_string_pretty(&self.inputs).unwrap());
             exit(0);
         }
+        response
     }
 }